@@ -8,7 +8,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 
             let first = digits.next().expect("should be a number");
 
-            match digits.last() {
+            match digits.next_back() {
                 Some(digit) => {
                     format!("{first}{digit}")
                 }
@@ -57,7 +57,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
             let first = digits.next().expect("should be a number");
 
-            match digits.last() {
+            match digits.next_back() {
                 Some(digit) => {
                     format!("{first}{digit}")
                 }
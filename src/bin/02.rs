@@ -8,7 +8,7 @@ use nom::{
     IResult,
 };
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, trim = true);
 
 struct Cube<'a> {
     color: &'a str,
@@ -49,23 +49,23 @@ impl<'a> Game<'a> {
     }
 }
 
-fn cube(input: &str) -> IResult<&str, Cube> {
+fn cube(input: &str) -> IResult<&str, Cube<'_>> {
     let (input, (amount, color)) = separated_pair(complete::u32, tag(" "), alpha1)(input)?;
     Ok((input, Cube { color, amount }))
 }
 
-fn hand(input: &str) -> IResult<&str, Vec<Cube>> {
+fn hand(input: &str) -> IResult<&str, Vec<Cube<'_>>> {
     let (input, cubes) = separated_list1(tag(", "), cube)(input)?;
     Ok((input, cubes))
 }
 
-fn game(input: &str) -> IResult<&str, Game> {
+fn game(input: &str) -> IResult<&str, Game<'_>> {
     let (input, id) = preceded(tag("Game "), digit1)(input)?;
     let (input, hands) = preceded(tag(": "), separated_list1(tag("; "), hand))(input)?;
     Ok((input, Game { id, hands }))
 }
 
-fn parse_games(input: &str) -> IResult<&str, Vec<Game>> {
+fn parse_games(input: &str) -> IResult<&str, Vec<Game<'_>>> {
     let (input, games) = separated_list1(line_ending, game)(input)?;
     Ok((input, games))
 }
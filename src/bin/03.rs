@@ -1,161 +1,69 @@
-use std::collections::{HashMap, HashSet};
-
+use advent_of_code::grid::Grid;
+use advent_of_code::span::{grid_tokens, located, Span, SpanIVec2};
 use glam::IVec2;
 use itertools::Itertools;
-use nom::{
-    branch::alt,
-    bytes::complete::{is_not, tag, take_till1},
-    character::complete::digit1,
-    combinator::iterator,
-    IResult, Parser,
-};
-use nom_locate::LocatedSpan;
+use nom::{character::complete::digit1, IResult};
 
 advent_of_code::solution!(3);
 
-type Span<'a> = LocatedSpan<&'a str>;
-type SpanIVec2<'a> = LocatedSpan<&'a str, IVec2>;
-
-#[derive(Debug, PartialEq)]
-enum Value<'a> {
-    Empty,
-    Symbol(SpanIVec2<'a>),
-    Number(SpanIVec2<'a>),
+fn parse_numbers(input: Span) -> IResult<Span, Vec<SpanIVec2>> {
+    grid_tokens(located(digit1))(input)
 }
 
-fn with_xy(span: Span) -> SpanIVec2 {
-    let x = span.get_column() as i32 - 1;
-    let y = span.location_line() as i32 - 1;
-    span.map_extra(|_| IVec2::new(x, y))
+fn is_symbol(c: char) -> bool {
+    c != '.' && !c.is_ascii_digit()
 }
 
-fn parse_grid(input: Span) -> IResult<Span, Vec<Value>> {
-    let mut it = iterator(
-        input,
-        alt((
-            digit1.map(|span| with_xy(span)).map(Value::Number),
-            is_not(".\n0123456789")
-                .map(|span| with_xy(span))
-                .map(Value::Symbol),
-            take_till1(|c: char| c.is_ascii_digit() || c != '.' && c != '\n').map(|_| Value::Empty),
-        )),
-    );
-
-    let parsed = it
-        .filter(|value| value != &Value::Empty)
-        .collect::<Vec<Value>>();
-    let res: IResult<_, _> = it.finish();
+/// The positions of the digits of a number.
+fn digit_positions<'a>(num: &'a SpanIVec2) -> impl Iterator<Item = IVec2> + 'a {
+    (0..num.fragment().len() as i32).map(|x_offset| num.extra + IVec2::new(x_offset, 0))
+}
 
-    res.map(|(input, _)| (input, parsed))
+fn value(num: &SpanIVec2) -> u32 {
+    num.fragment()
+        .parse::<u32>()
+        .expect("should be a valid number")
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, objects) = parse_grid(Span::new(input)).unwrap();
-    let symbol_set = objects
-        .iter()
-        .filter_map(|value| match value {
-            Value::Empty => None,
-            Value::Symbol(sym) => Some(sym.extra),
-            Value::Number(_) => None,
-        })
-        .collect::<HashSet<IVec2>>();
+    let (_, numbers) = parse_numbers(Span::new(input)).unwrap();
+    let schematic = Grid::parse(input, |c| c);
 
     Some(
-        objects
+        numbers
             .iter()
-            .filter_map(|value| {
-                let Value::Number(num) = value else {
-                    return None;
-                };
-
-                let surrounding_pos = [
-                    // East border
-                    IVec2::new(num.fragment().len() as i32, 0),
-                    // West border
-                    IVec2::new(-1, 0),
-                ]
-                .into_iter()
-                .chain(
-                    // North border
-                    (-1..=num.fragment().len() as i32).map(|x_offset| IVec2::new(x_offset, 1)),
-                )
-                .chain(
-                    // South border
-                    (-1..=num.fragment().len() as i32).map(|x_offset| IVec2::new(x_offset, -1)),
-                )
-                .map(|pos| pos + num.extra)
-                .collect::<Vec<IVec2>>();
-
-                surrounding_pos
-                    .iter()
-                    .any(|pos| symbol_set.contains(pos))
-                    .then_some(
-                        num.fragment()
-                            .parse::<u32>()
-                            .expect("should be a valid number"),
-                    )
+            .filter(|num| {
+                digit_positions(num)
+                    .flat_map(|pos| schematic.neighbours8(pos))
+                    .any(|pos| is_symbol(schematic[pos]))
             })
+            .map(value)
             .sum(),
     )
 }
 
-fn parse_grid_asterisks(input: Span) -> IResult<Span, Vec<Value>> {
-    let mut it = iterator(
-        input,
-        alt((
-            digit1.map(|span| with_xy(span)).map(Value::Number),
-            tag("*").map(|span| with_xy(span)).map(Value::Symbol),
-            take_till1(|c: char| c.is_ascii_digit() || c == '*').map(|_| Value::Empty),
-        )),
-    );
-
-    let parsed = it
-        .filter(|value| value != &Value::Empty)
-        .collect::<Vec<Value>>();
-    let res: IResult<_, _> = it.finish();
-
-    res.map(|(input, _)| (input, parsed))
-}
-
-const POSITIONS: [IVec2; 8] = [
-    IVec2::new(0, 1),
-    IVec2::new(1, 1),
-    IVec2::new(1, 0),
-    IVec2::new(1, -1),
-    IVec2::new(0, -1),
-    IVec2::new(-1, -1),
-    IVec2::new(-1, 0),
-    IVec2::new(-1, 1),
-];
-
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, objects) = parse_grid_asterisks(Span::new(input)).unwrap();
-    let num_map = objects
-        .iter()
-        .filter_map(|value| match value {
-            Value::Empty => None,
-            Value::Symbol(_) => None,
-            Value::Number(num) => Some((num.extra, num.fragment(), num.location_offset())),
-        })
-        .flat_map(|(v, fragment, id)| {
-            (v.x..(v.x + fragment.len() as i32)).map(move |x| (IVec2::new(x, v.y), (id, fragment)))
-        })
-        .collect::<HashMap<IVec2, (usize, &&str)>>();
+    let (_, numbers) = parse_numbers(Span::new(input)).unwrap();
+    let schematic = Grid::parse(input, |c| c);
+
+    // The index of the number that covers every cell.
+    let mut number_at = schematic.map(|_| None);
+    for (index, num) in numbers.iter().enumerate() {
+        for pos in digit_positions(num) {
+            number_at[pos] = Some(index);
+        }
+    }
 
     Some(
-        objects
+        schematic
             .iter()
-            .filter_map(|value| {
-                let Value::Symbol(sym) = value else {
-                    return None;
-                };
-
-                let matching_nums = POSITIONS
-                    .iter()
-                    .map(|pos| *pos + sym.extra)
-                    .filter_map(|border| num_map.get(&border))
+            .filter(|(_, c)| **c == '*')
+            .filter_map(|(pos, _)| {
+                let matching_nums = schematic
+                    .neighbours8(pos)
+                    .filter_map(|border| number_at[border])
                     .unique()
-                    .map(|(_, fragment)| fragment.parse::<u32>().expect("should be a valid number"))
+                    .map(|index| value(&numbers[index]))
                     .collect::<Vec<u32>>();
 
                 (matching_nums.len() == 2).then_some(matching_nums.iter().product::<u32>())
@@ -1,8 +1,8 @@
-use std::{
-    cmp::{max, min},
-    ops::Range,
-};
+use std::ops::Range;
 
+use advent_of_code::intervals::{IntervalSet, PiecewiseMap};
+use advent_of_code::rng::Rng;
+use itertools::Itertools;
 use nom::{
     bytes::complete::take_until,
     character::complete::{self, line_ending, space1},
@@ -13,148 +13,51 @@ use nom::{
 use nom_supreme::{tag::complete::tag, ParserExt};
 // use tracing::info;
 
-advent_of_code::solution!(5);
-
-#[derive(Debug)]
-struct Mapping {
-    source: Range<u64>,
-    destination: Range<u64>,
-}
-
-impl Mapping {
-    fn project_range(&self, source_range: Range<u64>) -> Range<u64> {
-        let start = self.destination.start + (source_range.start - self.source.start);
-        let end = self.destination.start + (source_range.end - self.source.start);
-        start..end
-    }
-
-    fn remainder(&self, b: &Range<u64>) -> Vec<Range<u64>> {
-        let intersection_range = self.intersection(b);
-
-        let mut ranges = Vec::new();
-        if intersection_range.start > b.start {
-            ranges.push(b.start..intersection_range.start)
-        }
-        if intersection_range.end < b.end {
-            ranges.push(intersection_range.end..b.end)
-        }
-        ranges
-    }
-
-    fn intersection(&self, b: &Range<u64>) -> Range<u64> {
-        max(self.source.start, b.start)..min(self.source.end, b.end)
-    }
-
-    fn is_intersecting(&self, b: &Range<u64>) -> bool {
-        (b.start <= self.source.end) && (self.source.start <= b.end)
-    }
-}
-
-#[derive(Debug)]
-struct ResourceMap(Vec<Mapping>);
-
-impl ResourceMap {
-    fn translate_number(&self, source_num: u64) -> u64 {
-        let translation = self
-            .0
-            .iter()
-            .find(|mapping| mapping.source.contains(&source_num));
-
-        match translation {
-            Some(mapping) => mapping.destination.start + (source_num - mapping.source.start),
-            None => source_num,
-        }
-    }
-
-    fn translate_range(&self, source_range: Range<u64>) -> Vec<Range<u64>> {
-        let bucket = self
-            .0
-            .iter()
-            .fold(Bucket::new(source_range), |mut bucket, mapping| {
-                let mut remaining = Vec::new();
-                bucket.remaining.into_iter().for_each(|range| {
-                    if mapping.is_intersecting(&range) {
-                        bucket
-                            .translated
-                            .push(mapping.project_range(mapping.intersection(&range)));
-                        remaining.append(&mut mapping.remainder(&range));
-                    } else {
-                        remaining.push(range);
-                    }
-                });
-                bucket.remaining = remaining;
-                bucket
-            });
-
-        bucket.concat()
-    }
-}
+advent_of_code::solution!(5, generator = generate, naive_two = naive::part_two);
 
-struct Bucket {
-    translated: Vec<Range<u64>>,
-    remaining: Vec<Range<u64>>,
-}
-
-impl Bucket {
-    fn new(initial: Range<u64>) -> Self {
-        Bucket {
-            translated: Vec::new(),
-            remaining: vec![initial],
-        }
-    }
-
-    fn concat(&self) -> Vec<Range<u64>> {
-        [self.translated.to_owned(), self.remaining.to_owned()].concat()
-    }
-}
-
-fn line(input: &str) -> IResult<&str, (Range<u64>, Range<u64>)> {
+fn line(input: &str) -> IResult<&str, (Range<u64>, u64)> {
     let (input, (destination, source, size)) = tuple((
         complete::u64,
         complete::u64.preceded_by(tag(" ")),
         complete::u64.preceded_by(tag(" ")),
     ))(input)?;
 
-    Ok((
-        input,
-        (source..source + size, destination..destination + size),
-    ))
+    Ok((input, (source..source + size, destination)))
 }
 
-fn resource_map(input: &str) -> IResult<&str, ResourceMap> {
+fn resource_map(input: &str) -> IResult<&str, PiecewiseMap<u64>> {
     take_until("map:")
         .precedes(tag("map:"))
-        .precedes(
-            many1(
-                line_ending
-                    .precedes(line)
-                    .map(|(source, destination)| Mapping {
-                        source,
-                        destination,
-                    }),
-            )
-            .map(ResourceMap),
-        )
+        .precedes(many1(line_ending.precedes(line)).map(PiecewiseMap::new))
         .parse(input)
 }
 
-fn parse_maps(input: &str) -> IResult<&str, Vec<ResourceMap>> {
+#[tracing::instrument(skip_all)]
+fn parse_maps(input: &str) -> IResult<&str, Vec<PiecewiseMap<u64>>> {
     many1(resource_map)(input)
 }
 
+/// The map from seeds to locations.
+fn almanac(maps: &[PiecewiseMap<u64>]) -> PiecewiseMap<u64> {
+    maps.iter().fold(PiecewiseMap::identity(), |almanac, map| {
+        almanac.compose(map)
+    })
+}
+
 fn parse_seeds(input: &str) -> IResult<&str, Vec<u64>> {
     tag("seeds: ")
         .precedes(separated_list1(space1, complete::u64))
         .parse(input)
 }
 
-fn parse_seed_ranges(input: &str) -> IResult<&str, Vec<Range<u64>>> {
+fn parse_seed_ranges(input: &str) -> IResult<&str, IntervalSet<u64>> {
     tag("seeds: ")
         .precedes(separated_list1(
             space1,
             separated_pair(complete::u64, space1, complete::u64)
                 .map(|(start, size)| start..start + size),
         ))
+        .map(IntervalSet::normalize)
         .parse(input)
 }
 
@@ -164,14 +67,9 @@ pub fn part_one(input: &str) -> Option<u64> {
     let (_, maps) = parse_maps(input).expect("should be parsable");
     // info!(?seeds);
     // info!(?maps);
+    let seed_to_location = almanac(&maps);
 
-    seeds
-        .iter()
-        .map(|seed| {
-            maps.iter()
-                .fold(*seed, |location, map| map.translate_number(location))
-        })
-        .min()
+    seeds.iter().map(|seed| seed_to_location.apply(*seed)).min()
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
@@ -179,16 +77,75 @@ pub fn part_two(input: &str) -> Option<u64> {
     let (_, maps) = parse_maps(input).expect("should be parsable");
     // info!(?seed_ranges);
 
-    let mut location_ranges = maps.iter().fold(seed_ranges, |ranges, map| {
-        ranges
-            .into_iter()
-            .flat_map(|range| map.translate_range(range))
-            .collect()
-    });
+    almanac(&maps).apply_set(&seed_ranges).min()
+}
+
+/// Generates ten seed ranges and the seven maps of the almanac, each with `size` mappings.
+/// Like in the puzzle, every map moves consecutive ranges around and keeps numbers outside of them.
+/// Small inputs use small numbers, from a size of 256 the numbers span 32 bits like in the puzzle.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const NAMES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+    let limit = (size as u64).saturating_pow(4).clamp(16, 1 << 32);
+
+    let seeds = (0..10)
+        .map(|_| {
+            let length = rng.range(1..limit / 16 + 1);
+            format!("{} {length}", rng.below(limit - length))
+        })
+        .join(" ");
+    let mut almanac = format!("seeds: {seeds}\n");
+
+    for (source, destination) in NAMES.iter().tuple_windows() {
+        let mut cuts: Vec<u64> = (0..=size.max(1)).map(|_| rng.below(limit)).collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+        if cuts.len() == 1 {
+            cuts.push(cuts[0] + 1);
+        }
+
+        let mut sources: Vec<Range<u64>> =
+            cuts.iter().tuple_windows().map(|(a, b)| *a..*b).collect();
+        rng.shuffle(&mut sources);
+
+        almanac.push_str(&format!("\n{source}-to-{destination} map:\n"));
+        let mut start = cuts[0];
+        for range in sources {
+            almanac.push_str(&format!(
+                "{start} {} {}\n",
+                range.start,
+                range.end - range.start
+            ));
+            start += range.end - range.start;
+        }
+    }
+    almanac
+}
+
+/// Tries every seed of the ranges, see `cargo check-ref`.
+mod naive {
+    use super::{parse_maps, parse_seed_ranges};
 
-    location_ranges.sort_by(|a, b| a.start.cmp(&b.start));
+    pub fn part_two(input: &str) -> Option<u64> {
+        let (input, seed_ranges) = parse_seed_ranges(input).expect("should be parsable");
+        let (_, maps) = parse_maps(input).expect("should be parsable");
 
-    Some(location_ranges[0].start)
+        seed_ranges
+            .ranges()
+            .iter()
+            .cloned()
+            .flatten()
+            .map(|seed| maps.iter().fold(seed, |location, map| map.apply(location)))
+            .min()
+    }
 }
 
 #[cfg(test)]
@@ -206,4 +163,12 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 20);
+        assert_eq!(input.matches("map:").count(), 7);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
 }
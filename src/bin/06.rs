@@ -7,7 +7,13 @@ use nom::{
 };
 use nom_supreme::ParserExt;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(
+    6,
+    naive_one = naive::part_one,
+    naive_two = naive::part_two,
+    variants_one = [part_one::quadratic],
+    variants_two = [part_two::quadratic],
+);
 
 fn nums(input: &str) -> IResult<&str, Vec<u64>> {
     is_not("0123456789")
@@ -28,6 +34,68 @@ fn join_nums(a: Vec<u64>) -> u64 {
         .expect("should be parsable")
 }
 
+/// Counts the ways to beat the record. The distance is symmetric around half the time,
+/// so it's enough to search for the shortest winning hold time.
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    let wins = |ms: u64| (time - ms) * ms > distance;
+
+    if !wins(time / 2) {
+        return 0;
+    }
+
+    let (mut losing, mut winning) = (0, time / 2);
+    while winning - losing > 1 {
+        let middle = losing + (winning - losing) / 2;
+        if wins(middle) {
+            winning = middle;
+        } else {
+            losing = middle;
+        }
+    }
+
+    time - 2 * winning + 1
+}
+
+/// The largest `root` with `root * root <= n`, estimated with floating point and corrected.
+fn isqrt(n: u128) -> u128 {
+    let squared_above = |root: u128| !matches!(root.checked_mul(root), Some(square) if square <= n);
+
+    let mut root = (n as f64).sqrt() as u128;
+    while squared_above(root) {
+        root -= 1;
+    }
+    while !squared_above(root + 1) {
+        root += 1;
+    }
+    root
+}
+
+/// Counts the ways to beat the record with the roots of `(time - ms) * ms = distance`, `(time ± √discriminant) / 2`.
+/// The integer square root only estimates the shortest winning hold time, which is then corrected,
+/// so unlike floating point roots the count stays exact for numbers above 2^53.
+fn ways_to_win_quadratic(time: u64, distance: u64) -> u64 {
+    let (time, distance) = (u128::from(time), u128::from(distance));
+    let wins = |ms: u128| (time - ms) * ms > distance;
+
+    let Some(discriminant) = (time * time).checked_sub(4 * distance) else {
+        return 0;
+    };
+
+    // the record has to be beaten, so hold times at the roots don't count.
+    let mut shortest = (time - isqrt(discriminant)) / 2;
+    while shortest > 0 && wins(shortest - 1) {
+        shortest -= 1;
+    }
+    while shortest <= time / 2 && !wins(shortest) {
+        shortest += 1;
+    }
+    if shortest > time / 2 {
+        return 0;
+    }
+
+    (time - 2 * shortest + 1) as u64
+}
+
 pub fn part_one(input: &str) -> Option<u32> {
     let (_, (times, distances)) = parse_numbers(input).expect("should be parsable");
 
@@ -35,9 +103,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         times
             .into_iter()
             .zip(distances)
-            .map(|(time, distance)| {
-                (1..time).filter(|ms| (time - ms) * ms > distance).count() as u32
-            })
+            .map(|(time, distance)| ways_to_win(time, distance) as u32)
             .product::<u32>(),
     )
 }
@@ -45,10 +111,65 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u64> {
     let (_, (times, distances)) = parse_numbers(input).expect("should be parsable");
 
-    let time = join_nums(times);
-    let distance = join_nums(distances);
+    Some(ways_to_win(join_nums(times), join_nums(distances)))
+}
+
+/// Variants that solve the quadratic equation, see `cargo solve 06 --variants`.
+mod part_one {
+    use super::{parse_numbers, ways_to_win_quadratic};
+
+    pub fn quadratic(input: &str) -> Option<u32> {
+        let (_, (times, distances)) = parse_numbers(input).expect("should be parsable");
 
-    Some((1..time).filter(|ms| (time - ms) * ms > distance).count() as u64)
+        Some(
+            times
+                .into_iter()
+                .zip(distances)
+                .map(|(time, distance)| ways_to_win_quadratic(time, distance) as u32)
+                .product::<u32>(),
+        )
+    }
+}
+
+mod part_two {
+    use super::{join_nums, parse_numbers, ways_to_win_quadratic};
+
+    pub fn quadratic(input: &str) -> Option<u64> {
+        let (_, (times, distances)) = parse_numbers(input).expect("should be parsable");
+
+        Some(ways_to_win_quadratic(
+            join_nums(times),
+            join_nums(distances),
+        ))
+    }
+}
+
+/// Tries every millisecond, see `cargo check-ref`.
+mod naive {
+    use super::{join_nums, parse_numbers};
+
+    pub fn part_one(input: &str) -> Option<u32> {
+        let (_, (times, distances)) = parse_numbers(input).expect("should be parsable");
+
+        Some(
+            times
+                .into_iter()
+                .zip(distances)
+                .map(|(time, distance)| {
+                    (1..time).filter(|ms| (time - ms) * ms > distance).count() as u32
+                })
+                .product::<u32>(),
+        )
+    }
+
+    pub fn part_two(input: &str) -> Option<u64> {
+        let (_, (times, distances)) = parse_numbers(input).expect("should be parsable");
+
+        let time = join_nums(times);
+        let distance = join_nums(distances);
+
+        Some((1..time).filter(|ms| (time - ms) * ms > distance).count() as u64)
+    }
 }
 
 #[cfg(test)]
@@ -66,4 +187,34 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(71503));
     }
+
+    #[test]
+    fn test_ways_to_win() {
+        for time in 0..40 {
+            for distance in 0..time * time {
+                let expected = (1..time).filter(|ms| (time - ms) * ms > distance).count();
+                assert_eq!(ways_to_win(time, distance), expected as u64);
+                assert_eq!(ways_to_win_quadratic(time, distance), expected as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ways_to_win_above_f64_precision() {
+        // a floating point square root counts 5790 here.
+        let (time, distance) = (7_662_906_131, 14_680_032_593_120_969_385);
+        assert_eq!(ways_to_win(time, distance), 5788);
+        assert_eq!(ways_to_win_quadratic(time, distance), 5788);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        let max = u128::from(u64::MAX);
+        assert_eq!(isqrt(max * max), max);
+        assert_eq!(isqrt(max * max - 1), max - 1);
+        assert_eq!(isqrt(u128::MAX), max);
+    }
 }
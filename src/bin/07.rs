@@ -1,17 +1,9 @@
-use std::{cmp::Ordering, collections::HashMap, ops::Deref, sync::Mutex};
+use std::ops::Deref;
 
+use advent_of_code::rng::Rng;
 use itertools::Itertools;
-use lazy_static::lazy_static;
 
-advent_of_code::solution!(7);
-
-lazy_static! {
-    static ref OPTIONS: Mutex<HashMap<String, bool>> = Mutex::new({
-        let mut m = HashMap::new();
-        m.insert(String::from("JOKERS_ENABLED"), false);
-        m
-    });
-}
+advent_of_code::solution!(7, generator = generate);
 
 #[derive(Debug, PartialEq, Eq)]
 enum Hand<'a> {
@@ -44,7 +36,7 @@ impl<'a> Hand<'a> {
         }
     }
 
-    fn card_rankings(&self) -> Vec<u32> {
+    fn card_rankings(&self, jokers_enabled: bool) -> Vec<u32> {
         // For part two, we'll treat Jokers as 1.
         self.value()
             .chars()
@@ -54,7 +46,7 @@ impl<'a> Hand<'a> {
                 'Q' => 12,
                 'T' => 10,
                 'J' => {
-                    if *OPTIONS.lock().unwrap().get("JOKERS_ENABLED").unwrap() {
+                    if jokers_enabled {
                         1
                     } else {
                         11
@@ -66,34 +58,17 @@ impl<'a> Hand<'a> {
     }
 }
 
-impl<'a> Ord for Hand<'a> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match self.score().cmp(&other.score()) {
-            Ordering::Equal => self.card_rankings().cmp(&other.card_rankings()),
-            ordering => ordering,
-        }
-    }
-}
-
-impl<'a> PartialOrd for Hand<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq)]
 struct Game<'a> {
     hand: Hand<'a>,
     bid: u32,
 }
 
-fn determine_hand_type(hand: &str) -> Hand {
+fn determine_hand_type(hand: &str, jokers_enabled: bool) -> Hand<'_> {
     use Hand::*;
 
     let mut counts = hand.chars().counts();
 
-    let jokers_enabled = *OPTIONS.lock().unwrap().get("JOKERS_ENABLED").unwrap();
-
     // For part two, we combine Js into the highest card count.
     if hand.contains('J') && jokers_enabled {
         let joker_count = *counts.get(&'J').unwrap();
@@ -122,69 +97,78 @@ fn determine_hand_type(hand: &str) -> Hand {
     }
 }
 
-fn parse_games(input: &str) -> Vec<Game> {
+fn parse_games(input: &str, jokers_enabled: bool) -> Vec<Game<'_>> {
     input
         .lines()
         .map(|line| {
             let (hand, bid) = line.split_once(' ').unwrap();
             Game {
-                hand: determine_hand_type(hand),
+                hand: determine_hand_type(hand, jokers_enabled),
                 bid: bid.parse::<u32>().unwrap(),
             }
         })
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = parse_games(input);
-    // dbg!(games);
+/// Ranks the games by hand type, then by their cards and bid, and sums every bid times its rank.
+fn total_winnings(input: &str, jokers_enabled: bool) -> u32 {
+    let games = parse_games(input, jokers_enabled);
+
+    games
+        .iter()
+        .sorted_by_cached_key(|game| {
+            (
+                game.hand.score(),
+                game.hand.card_rankings(jokers_enabled),
+                game.bid,
+            )
+        })
+        .enumerate()
+        .map(|(index, game)| game.bid * (index as u32 + 1))
+        .sum()
+}
 
-    Some(
-        games
-            .iter()
-            .sorted()
-            .enumerate()
-            .map(|(index, game)| game.bid * (index as u32 + 1))
-            .sum(),
-    )
+pub fn part_one(input: &str) -> Option<u32> {
+    Some(total_winnings(input, false))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    OPTIONS
-        .lock()
-        .unwrap()
-        .entry(String::from("JOKERS_ENABLED"))
-        .and_modify(|v| *v = true);
+    Some(total_winnings(input, true))
+}
 
-    let games = parse_games(input);
+/// Generates `size` hands of five random cards, each with a bid like in the puzzle.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let cards: Vec<char> = "23456789TJQKA".chars().collect();
 
-    Some(
-        games
-            .iter()
-            .sorted()
-            .enumerate()
-            .map(|(index, game)| game.bid * (index as u32 + 1))
-            .sum(),
-    )
+    (0..size)
+        .map(|_| {
+            let hand: String = (0..5).map(|_| *rng.choose(&cards)).collect();
+            format!("{hand} {}\n", rng.range(1..1001))
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use serial_test::serial;
-
     use super::*;
 
     #[test]
-    #[serial]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
-    #[serial]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(5905));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input.lines().count(), 100);
+        assert!(part_one(&input).is_some());
+        assert!(part_two(&input).is_some());
+    }
 }
@@ -20,7 +20,7 @@ enum Direction {
     Right,
 }
 
-fn node(input: &str) -> IResult<&str, (&str, Edges)> {
+fn node(input: &str) -> IResult<&str, (&str, Edges<'_>)> {
     separated_pair(
         alphanumeric1,
         tag(" = "),
@@ -32,7 +32,7 @@ fn node(input: &str) -> IResult<&str, (&str, Edges)> {
     )(input)
 }
 
-fn parse_nodes(input: &str) -> IResult<&str, BTreeMap<&str, Edges>> {
+fn parse_nodes(input: &str) -> IResult<&str, BTreeMap<&str, Edges<'_>>> {
     fold_many1(
         preceded(many1(line_ending), node),
         BTreeMap::new,
@@ -1,23 +1,12 @@
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-};
+use std::collections::HashSet;
+use std::{io, path::Path};
 
+use advent_of_code::grid::{Grid, NEIGHBOURS4};
+use advent_of_code::rng::Rng;
+use advent_of_code::visualize::{Canvas, Color, Frames};
 use glam::IVec2;
-use nom::{
-    branch::alt,
-    bytes::complete::{tag, take_while_m_n},
-    character::complete::multispace0,
-    combinator::iterator,
-    sequence::terminated,
-    IResult, Parser,
-};
-use nom_locate::LocatedSpan;
 
-advent_of_code::solution!(10);
-
-type Span<'a> = LocatedSpan<&'a str>;
-type SpanIVec2<'a> = LocatedSpan<&'a str, IVec2>;
+advent_of_code::solution!(10, generator = generate, visualizer = visualize);
 
 #[derive(Clone, Debug, PartialEq)]
 enum Pipe {
@@ -31,185 +20,299 @@ enum Pipe {
     SouthWest,
 }
 
-#[derive(Clone, Debug)]
-struct Square<'a> {
-    item: Pipe,
-    data: SpanIVec2<'a>,
-}
+impl Pipe {
+    fn from_char(c: char) -> Pipe {
+        match c {
+            '.' => Pipe::Empty,
+            'S' => Pipe::Start,
+            '|' => Pipe::NorthSouth,
+            '-' => Pipe::EastWest,
+            'L' => Pipe::NorthEast,
+            'J' => Pipe::NorthWest,
+            'F' => Pipe::SouthEast,
+            '7' => Pipe::SouthWest,
+            _ => panic!("invalid character!"),
+        }
+    }
 
-impl<'a> Square<'a> {
-    fn fragment(&self) -> &str {
-        self.data.fragment()
-    }
-
-    fn position(&self) -> IVec2 {
-        self.data.extra
-    }
-
-    fn is_boundary(&self, loop_squares: &HashSet<IVec2>) -> bool {
-        loop_squares.contains(&self.position())
-            && matches!(
-                self.item,
-                Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest
-            )
-    }
-
-    fn surrounding_pos(&self) -> Vec<IVec2> {
-        let positions = match self.item {
-            Pipe::Empty => vec![],
-            Pipe::NorthSouth => vec![IVec2::new(0, -1), IVec2::new(0, 1)],
-            Pipe::EastWest => vec![IVec2::new(1, 0), IVec2::new(-1, 0)],
-            Pipe::NorthEast => vec![IVec2::new(0, -1), IVec2::new(1, 0)],
-            Pipe::NorthWest => vec![IVec2::new(0, -1), IVec2::new(-1, 0)],
-            Pipe::SouthEast => vec![IVec2::new(0, 1), IVec2::new(1, 0)],
-            Pipe::SouthWest => vec![IVec2::new(0, 1), IVec2::new(-1, 0)],
-            _ => vec![
-                IVec2::new(-1, 0),
-                IVec2::new(0, 1),
-                IVec2::new(1, 0),
-                IVec2::new(-1, 0),
-            ],
-        };
-
-        positions
-            .into_iter()
-            .map(|pos| pos + self.position())
-            .collect::<Vec<IVec2>>()
+    /// The box drawing character of the pipe, for visualizations.
+    fn symbol(&self) -> char {
+        match self {
+            Pipe::Empty => '.',
+            Pipe::Start => 'S',
+            Pipe::NorthSouth => '│',
+            Pipe::EastWest => '─',
+            Pipe::NorthEast => '└',
+            Pipe::NorthWest => '┘',
+            Pipe::SouthEast => '┌',
+            Pipe::SouthWest => '┐',
+        }
     }
-}
 
-fn determine_pipe(c: &str) -> Pipe {
-    match c {
-        "|" => Pipe::NorthSouth,
-        "-" => Pipe::EastWest,
-        "L" => Pipe::NorthEast,
-        "J" => Pipe::NorthWest,
-        "F" => Pipe::SouthEast,
-        "7" => Pipe::SouthWest,
-        _ => panic!("invalid character!"),
+    fn is_boundary(&self) -> bool {
+        matches!(self, Pipe::NorthSouth | Pipe::NorthEast | Pipe::NorthWest)
     }
-}
 
-fn with_xy(span: Span) -> SpanIVec2 {
-    let x = span.get_column() as i32 - 1;
-    let y = span.location_line() as i32 - 1;
-    span.map_extra(|_| IVec2::new(x, y))
+    /// The pipe that connects in the two `directions`.
+    fn from_connections(directions: &[IVec2]) -> Pipe {
+        [
+            Pipe::NorthSouth,
+            Pipe::EastWest,
+            Pipe::NorthEast,
+            Pipe::NorthWest,
+            Pipe::SouthEast,
+            Pipe::SouthWest,
+        ]
+        .into_iter()
+        .find(|pipe| {
+            directions.len() == 2
+                && pipe
+                    .connections()
+                    .iter()
+                    .all(|direction| directions.contains(direction))
+        })
+        .expect("the start should connect to exactly two pipes")
+    }
+
+    /// The offsets of the tiles the pipe connects to.
+    fn connections(&self) -> &'static [IVec2] {
+        match self {
+            Pipe::Empty => &[],
+            Pipe::NorthSouth => &[IVec2::NEG_Y, IVec2::Y],
+            Pipe::EastWest => &[IVec2::X, IVec2::NEG_X],
+            Pipe::NorthEast => &[IVec2::NEG_Y, IVec2::X],
+            Pipe::NorthWest => &[IVec2::NEG_Y, IVec2::NEG_X],
+            Pipe::SouthEast => &[IVec2::Y, IVec2::X],
+            Pipe::SouthWest => &[IVec2::Y, IVec2::NEG_X],
+            Pipe::Start => &NEIGHBOURS4,
+        }
+    }
 }
 
-fn parse_grid(input: Span) -> IResult<Span, HashMap<IVec2, Square>> {
-    let mut it = iterator(
-        input,
-        terminated(
-            alt((
-                tag("S").map(|span| with_xy(span)).map(|span| Square {
-                    item: Pipe::Start,
-                    data: span,
-                }),
-                take_while_m_n(1, 1, |c: char| ".\nS".chars().all(|other| other != c))
-                    .map(|span| with_xy(span))
-                    .map(|span| Square {
-                        item: determine_pipe(span.fragment()),
-                        data: span,
-                    }),
-                tag(".").map(|span| with_xy(span)).map(|span| Square {
-                    item: Pipe::Empty,
-                    data: span,
-                }),
-            )),
-            multispace0,
-        ),
-    );
+/// Parses the field and replaces the start with the pipe that connects it to the loop.
+fn parse_grid(input: &str) -> (IVec2, Grid<Pipe>) {
+    let mut grid = Grid::parse(input, Pipe::from_char);
+    let start = grid
+        .position(|pipe| *pipe == Pipe::Start)
+        .expect("should have start square");
 
-    let parsed: HashMap<IVec2, Square> = it.fold(HashMap::new(), |mut acc, square| {
-        acc.insert(square.data.extra, square);
-        acc
-    });
+    let directions: Vec<IVec2> = Pipe::Start
+        .connections()
+        .iter()
+        .copied()
+        .filter(|direction| {
+            grid.get(start + *direction)
+                .is_some_and(|pipe| pipe.connections().contains(&-*direction))
+        })
+        .collect();
+    grid[start] = Pipe::from_connections(&directions);
 
-    let res: IResult<_, _> = it.finish();
+    (start, grid)
+}
 
-    res.map(|(input, _)| (input, parsed))
+/// The tiles of the grid that the pipe at `pos` connects to.
+fn connected(grid: &Grid<Pipe>, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+    grid[pos]
+        .connections()
+        .iter()
+        .map(move |offset| pos + *offset)
+        .filter(|next| grid.contains(*next))
 }
 
-fn loop_squares(grid: &HashMap<IVec2, Square>) -> HashSet<IVec2> {
-    let start_square = grid
-        .values()
-        .find(|square| matches!(square.item, Pipe::Start))
-        .expect("should have start square");
+fn loop_squares(grid: &Grid<Pipe>, start: IVec2) -> HashSet<IVec2> {
+    loop_path(grid, start).into_iter().collect()
+}
 
-    let mut curr_square = start_square
-        .surrounding_pos()
-        .into_iter()
-        .filter_map(|pos| grid.get(&pos))
-        .find(|square| square.surrounding_pos().contains(&start_square.position()))
+/// The positions of the loop in the order they are walked, starting at the start square.
+fn loop_path(grid: &Grid<Pipe>, start: IVec2) -> Vec<IVec2> {
+    let mut curr_pos = connected(grid, start)
+        .next()
         .expect("should have a compatible pipe");
 
-    let mut prev_pos = start_square.position();
+    let mut prev_pos = start;
 
-    let mut set = HashSet::from([start_square.position()]);
+    let mut path = vec![start];
 
-    loop {
-        let placeholder = curr_square.position();
+    while curr_pos != start {
+        let placeholder = curr_pos;
 
-        curr_square = curr_square
-            .surrounding_pos()
-            .into_iter()
-            .filter(|pos| *pos != prev_pos)
-            .filter_map(|pos| grid.get(&pos))
-            .next()
+        curr_pos = connected(grid, curr_pos)
+            .find(|pos| *pos != prev_pos)
             .expect("should have compatible pipe");
 
         prev_pos = placeholder;
-        set.insert(placeholder);
-
-        if curr_square.fragment() == "S" {
-            break;
-        }
+        path.push(placeholder);
     }
 
-    set
+    path
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (_, grid) = parse_grid(Span::new(input)).expect("should be parsable");
+    let (start, grid) = parse_grid(input);
 
-    let set = loop_squares(&grid);
+    let set = loop_squares(&grid, start);
 
     Some(set.len() as u32 / 2)
 }
 
+/// The tiles that are enclosed by the loop, found by counting the loop crossings to their left.
+fn enclosed_tiles(grid: &Grid<Pipe>, set: &HashSet<IVec2>) -> Vec<IVec2> {
+    let mut enclosed = vec![];
+    let mut parity = 0;
+
+    for (pos, pipe) in grid.iter() {
+        if pos.x == 0 {
+            parity = 0;
+        }
+
+        if set.contains(&pos) {
+            if pipe.is_boundary() {
+                parity += 1;
+            }
+        } else if parity % 2 == 1 {
+            enclosed.push(pos);
+        }
+    }
+
+    enclosed
+}
+
 pub fn part_two(input: &str) -> Option<u32> {
-    let (_, grid) = parse_grid(Span::new(input)).expect("should be parsable");
+    let (start, grid) = parse_grid(input);
 
-    let max_dims = grid
-        .keys()
-        .max_by(|a, b| match a.x.cmp(&b.x) {
-            Ordering::Equal => a.y.cmp(&b.y),
-            ordering => ordering,
-        })
-        .expect("should have max dimensions");
+    let set = loop_squares(&grid, start);
 
-    let length = max_dims.x;
-    let height = max_dims.y;
+    Some(enclosed_tiles(&grid, &set).len() as u32)
+}
 
-    let set = loop_squares(&grid);
+/// Draws the loop as it is walked and then highlights the enclosed tiles.
+/// Writes the result to `10.svg`, the steps to `10-frames/` and prints the result to the terminal.
+fn visualize(input: &str, dir: &Path) -> io::Result<()> {
+    const FRAMES: usize = 50;
 
-    let mut inside_ground = 0;
+    let (start, grid) = parse_grid(input);
+    let path = loop_path(&grid, start);
+    let set: HashSet<IVec2> = path.iter().copied().collect();
 
-    (0..=height).for_each(|y| {
-        let mut parity = 0;
-        (0..=length).for_each(|x| {
-            let pos = IVec2::from_array([x, y]);
+    let mut canvas = Canvas::from_text(input);
+    for pos in grid.positions().filter(|pos| !set.contains(pos)) {
+        canvas.color(pos, Color::GRAY);
+    }
 
-            let square = grid.get(&pos).expect("should be valid position");
-            if square.is_boundary(&set) {
-                parity += 1;
-            } else if !set.contains(&square.position()) && parity % 2 == 1 {
-                inside_ground += 1;
-            }
-        })
-    });
+    let mut frames = Frames::default();
+    let step = (path.len() / FRAMES).max(1);
+    for (index, pos) in path.iter().enumerate() {
+        canvas.set(*pos, grid[*pos].symbol());
+        canvas.color(*pos, Color::BLUE);
+        if index % step == 0 {
+            frames.push(canvas.clone());
+        }
+    }
 
-    Some(inside_ground)
+    canvas.set(start, 'S');
+    canvas.color(start, Color::YELLOW);
+    for pos in enclosed_tiles(&grid, &set) {
+        canvas.highlight(pos, Color::GREEN);
+        canvas.color(pos, Color::BLACK);
+    }
+    frames.push(canvas.clone());
+
+    print!("{}", canvas.to_ansi());
+
+    let image = dir.join("10.svg");
+    canvas.write_svg(&image)?;
+    let frames_dir = dir.join("10-frames");
+    frames.write_svgs(&frames_dir, "step")?;
+    println!(
+        "Wrote \"{}\" and {} steps to \"{}\".",
+        image.display(),
+        frames.len(),
+        frames_dir.display()
+    );
+
+    Ok(())
+}
+
+/// Generates a square field with a side of `size`, where the loop is the outline of a random tree.
+/// The tree connects 2x2 blocks on a coarse grid, which gives a loop without holes that never touches itself.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let cells = (size.saturating_sub(1) / 3).max(1);
+    let side = size.max(3 * cells + 1);
+
+    // Grows a random spanning tree over the coarse grid, `filled` holds the unit squares of its outline.
+    let block =
+        |cell: IVec2| [(1, 1), (2, 1), (1, 2), (2, 2)].map(|(x, y)| cell * 3 + IVec2::new(x, y));
+    let mut filled: HashSet<IVec2> = HashSet::from(block(IVec2::ZERO));
+    let mut visited = HashSet::from([IVec2::ZERO]);
+    let mut active = vec![IVec2::ZERO];
+    let directions = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+    while !active.is_empty() {
+        let index = rng.index(active.len());
+        let cell = active[index];
+        let unvisited: Vec<IVec2> = directions
+            .iter()
+            .map(|direction| cell + *direction)
+            .filter(|next| next.min_element() >= 0 && next.max_element() < cells as i32)
+            .filter(|next| !visited.contains(next))
+            .collect();
+
+        if unvisited.is_empty() {
+            active.swap_remove(index);
+            continue;
+        }
+
+        let next = *rng.choose(&unvisited);
+        let gap = cell.max(next) * 3;
+        for offset in [1, 2] {
+            filled.insert(if next.x == cell.x {
+                IVec2::new(cell.x * 3 + offset, gap.y)
+            } else {
+                IVec2::new(gap.x, cell.y * 3 + offset)
+            });
+        }
+        filled.extend(block(next));
+        visited.insert(next);
+        active.push(next);
+    }
+
+    // A tile sits on a corner of the unit squares, it connects along the edges between a filled and an empty square.
+    let is_filled = |x: i32, y: i32| filled.contains(&IVec2::new(x, y));
+    let pipe = |x: i32, y: i32| {
+        let north = is_filled(x - 1, y - 1) != is_filled(x, y - 1);
+        let south = is_filled(x - 1, y) != is_filled(x, y);
+        let west = is_filled(x - 1, y - 1) != is_filled(x - 1, y);
+        let east = is_filled(x, y - 1) != is_filled(x, y);
+        match (north, south, west, east) {
+            (true, true, false, false) => Some('|'),
+            (false, false, true, true) => Some('-'),
+            (true, false, false, true) => Some('L'),
+            (true, false, true, false) => Some('J'),
+            (false, true, true, false) => Some('7'),
+            (false, true, false, true) => Some('F'),
+            _ => None,
+        }
+    };
+
+    let mut field = Grid::filled(side, side, '.');
+    for pos in field.positions() {
+        field[pos] = pipe(pos.x, pos.y)
+            .unwrap_or_else(|| *rng.choose(&['.', '.', '|', '-', 'L', 'J', '7', 'F']));
+    }
+
+    // Only the loop may connect to the start.
+    let starts: Vec<IVec2> = field
+        .positions()
+        .filter(|tile| pipe(tile.x, tile.y).is_some())
+        .collect();
+    let start = *rng.choose(&starts);
+    field[start] = 'S';
+    for tile in NEIGHBOURS4.map(|direction| start + direction) {
+        if pipe(tile.x, tile.y).is_none() && field.contains(tile) {
+            field[tile] = '.';
+        }
+    }
+
+    field.to_string()
 }
 
 #[cfg(test)]
@@ -231,4 +334,36 @@ mod tests {
         ));
         assert_eq!(result, Some(10));
     }
+
+    #[test]
+    fn test_part_two_start_connects_north() {
+        let result = part_two(".....\n.F-7.\n.|.|.\n.S-J.\n.....\n");
+        assert_eq!(result, Some(1));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 40);
+        assert_eq!(input.lines().count(), 40);
+        assert!(input.lines().all(|line| line.len() == 40));
+        assert!(part_one(&input).unwrap() > 0);
+        assert!(part_two(&input).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let dir = std::env::temp_dir().join(format!("aoc-10-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        visualize(
+            &advent_of_code::template::read_file_part("examples", DAY, 2),
+            &dir,
+        )
+        .unwrap();
+
+        let svg = std::fs::read_to_string(dir.join("10.svg")).unwrap();
+        // the enclosed tiles are highlighted.
+        assert_eq!(svg.matches("<rect x=").count(), 10);
+        assert!(dir.join("10-frames/step-001.svg").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
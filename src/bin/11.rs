@@ -1,26 +1,36 @@
 use std::collections::HashSet;
 
-use nom::{
-    bytes::complete::{tag, take_until},
-    multi::fold_many1,
-    sequence::preceded,
-    IResult, Parser,
-};
-use nom_locate::LocatedSpan;
+use advent_of_code::grid::Grid;
+use advent_of_code::rng::Rng;
+use glam::IVec2;
 
-advent_of_code::solution!(11);
-
-type Span<'a> = LocatedSpan<&'a str>;
+advent_of_code::solution!(
+    11,
+    generator = generate,
+    naive_one = naive::part_one,
+    naive_two = naive::part_two,
+);
 
 #[derive(Debug, Clone)]
-struct Grid {
+struct Image {
     galaxies: HashSet<(usize, usize)>,
     galaxy_rows: HashSet<usize>,
     galaxy_cols: HashSet<usize>,
 }
 
-impl Grid {
+impl Image {
     fn distances(&self, factor: usize) -> usize {
+        axis_distances(
+            self.galaxies.iter().map(|galaxy| galaxy.0).collect(),
+            factor,
+        ) + axis_distances(
+            self.galaxies.iter().map(|galaxy| galaxy.1).collect(),
+            factor,
+        )
+    }
+
+    /// Sums the distance of every pair, expanding the rows and columns between them one by one.
+    fn naive_distances(&self, factor: usize) -> usize {
         let mut total = 0;
         for galaxy_a in &self.galaxies {
             for galaxy_b in &self.galaxies {
@@ -59,44 +69,87 @@ impl Grid {
     }
 }
 
-fn as_xy(span: Span) -> (usize, usize) {
-    // Positions are 1-indexed
-    let x = span.get_column() - 1;
-    let y = span.location_line() as usize - 1;
-    (x, y)
+/// Sums the distances between all pairs of coordinates on one axis, where every empty line counts `factor` times.
+fn axis_distances(mut coordinates: Vec<usize>, factor: usize) -> usize {
+    coordinates.sort_unstable();
+
+    let (mut total, mut sum, mut expanded) = (0, 0, 0);
+    for (index, pair) in coordinates.windows(2).enumerate() {
+        let gap = pair[1] - pair[0];
+        if gap > 0 {
+            // every line between two occupied lines is empty.
+            expanded += 1 + (gap - 1) * factor;
+        }
+        total += expanded * (index + 1) - sum;
+        sum += expanded;
+    }
+    total
 }
 
-fn parse_grid(input: Span) -> IResult<Span, Grid> {
-    let (input, galaxies) = fold_many1(
-        preceded(take_until("#"), tag("#").map(|span| as_xy(span))),
-        HashSet::new,
-        |mut acc, galaxy| {
-            acc.insert(galaxy);
-            acc
-        },
-    )(input)?;
+fn parse_image(input: &str) -> Image {
+    let grid = Grid::parse(input, |c| c == '#');
+    let galaxies: HashSet<(usize, usize)> = grid
+        .iter()
+        .filter(|(_, is_galaxy)| **is_galaxy)
+        .map(|(pos, _)| (pos.x as usize, pos.y as usize))
+        .collect();
+    assert!(!galaxies.is_empty(), "should have a galaxy");
 
     let galaxy_rows = galaxies.iter().map(|galaxy| galaxy.0).collect();
     let galaxy_cols = galaxies.iter().map(|galaxy| galaxy.1).collect();
 
-    Ok((
-        input,
-        Grid {
-            galaxies,
-            galaxy_rows,
-            galaxy_cols,
-        },
-    ))
+    Image {
+        galaxies,
+        galaxy_rows,
+        galaxy_cols,
+    }
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (_, grid) = parse_grid(Span::new(input)).expect("should be parsable");
-    Some(grid.distances(2))
+    let image = parse_image(input);
+    Some(image.distances(2))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (_, grid) = parse_grid(Span::new(input)).expect("should be parsable");
-    Some(grid.distances(1_000_000))
+    let image = parse_image(input);
+    Some(image.distances(1_000_000))
+}
+
+/// Generates a square image with a side of `size`, where about one in eight rows and columns is empty.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let empty_rows: Vec<bool> = (0..size).map(|_| rng.chance(1, 8)).collect();
+    let empty_cols: Vec<bool> = (0..size).map(|_| rng.chance(1, 8)).collect();
+
+    let mut image = Grid::filled(size, size, '.');
+    for pos in image.positions() {
+        let is_galaxy =
+            !empty_rows[pos.y as usize] && !empty_cols[pos.x as usize] && rng.chance(1, 40);
+        if is_galaxy {
+            image[pos] = '#';
+        }
+    }
+
+    // The puzzle always has a galaxy.
+    if size > 0 && image.position(|c| *c == '#').is_none() {
+        image[IVec2::new(rng.index(size) as i32, rng.index(size) as i32)] = '#';
+    }
+
+    image.to_string()
+}
+
+/// Expands every row and column between two galaxies on its own, see `cargo check-ref`.
+mod naive {
+    use super::parse_image;
+
+    pub fn part_one(input: &str) -> Option<usize> {
+        let image = parse_image(input);
+        Some(image.naive_distances(2))
+    }
+
+    pub fn part_two(input: &str) -> Option<usize> {
+        let image = parse_image(input);
+        Some(image.naive_distances(1_000_000))
+    }
 }
 
 #[cfg(test)]
@@ -114,4 +167,13 @@ mod tests {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(82000210));
     }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 60);
+        assert_eq!(input.lines().count(), 60);
+        assert!(input.lines().all(|line| line.len() == 60));
+        assert!(part_one(&input).unwrap() > 0);
+        assert!(part_two(&input).unwrap() > part_one(&input).unwrap());
+    }
 }
@@ -1,5 +1,6 @@
 use std::error::Error;
 use std::fmt::Display;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
@@ -85,15 +86,31 @@ pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields the days of advent within a range that includes both ends, e.g. `day!(3)..=day!(7)`,
+/// like the `3..7` ranges of a [`DaySet`](crate::template::day_set::DaySet).
+pub fn all_days_in(range: RangeInclusive<Day>) -> AllDays {
+    AllDays::range(range)
+}
+
+/// An iterator that yields every day of advent from the 1st to the 25th, or the days within a range.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::range(Day(1)..=Day(25))
+    }
+
+    /// Creates an iterator over the days within a range that includes both ends.
+    pub fn range(range: RangeInclusive<Day>) -> Self {
+        let (first, last) = range.into_inner();
+        Self {
+            current: first.0,
+            last: last.0,
+        }
     }
 }
 
@@ -101,10 +118,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 or a valid day and stops at a valid day, so the value is in range.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,7 +151,7 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, all_days_in, AllDays, Day};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +184,17 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_in_range() {
+        let days = |iter: AllDays| iter.map(Day::into_inner).collect::<Vec<_>>();
+
+        assert_eq!(days(all_days_in(Day(3)..=Day(7))), vec![3, 4, 5, 6, 7]);
+        assert_eq!(days(all_days_in(Day(23)..=Day(25))), vec![23, 24, 25]);
+        assert_eq!(days(all_days_in(Day(1)..=Day(1))), vec![1]);
+        assert!(days(AllDays::range(Day(7)..=Day(3))).is_empty());
+        assert_eq!(days(AllDays::range(Day(1)..=Day(25))), days(all_days()));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
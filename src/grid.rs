@@ -0,0 +1,382 @@
+//! A dense two dimensional grid, for the many puzzles whose input is a map of characters.
+//! Cells are stored row by row and indexed by `IVec2` with `x` as the column and `y` as the row,
+//! the same as the [`Canvas`](crate::visualize::Canvas) of visualizations.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use glam::IVec2;
+
+/// The offsets of the orthogonal neighbours, clockwise starting north.
+pub const NEIGHBOURS4: [IVec2; 4] = [IVec2::NEG_Y, IVec2::X, IVec2::Y, IVec2::NEG_X];
+
+/// The offsets of the orthogonal and diagonal neighbours, clockwise starting north.
+pub const NEIGHBOURS8: [IVec2; 8] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(1, 0),
+    IVec2::new(1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, 0),
+    IVec2::new(-1, -1),
+];
+
+/// A rectangular grid of cells.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// # use glam::IVec2;
+/// let grid = Grid::parse("..#\n.#.\n", |c| c == '#');
+/// assert!(grid[IVec2::new(2, 0)]);
+/// assert_eq!(grid.neighbours4(IVec2::ZERO).count(), 2);
+/// assert_eq!(grid.to_string_with(|cell| if *cell { '#' } else { '.' }), "..#\n.#.\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row.
+    ///
+    /// # Panics
+    /// Panics if there are not `width * height` cells.
+    #[must_use]
+    pub fn from_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "a {width}x{height} grid should have {} cells",
+            width * height
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid where every cell is `value`.
+    #[must_use]
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_cells(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid with one row per line, mapping every character to a cell.
+    /// Trailing empty lines are ignored.
+    ///
+    /// # Panics
+    /// Panics if the lines don't all have the same length.
+    pub fn parse(text: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::with_capacity(text.len());
+
+        for line in text.trim_end_matches(['\n', '\r']).lines() {
+            let start = cells.len();
+            cells.extend(line.chars().map(&mut cell));
+            let line_width = cells.len() - start;
+            let width = *width.get_or_insert(line_width);
+            assert_eq!(
+                line_width,
+                width,
+                "line {} should have {width} characters",
+                height + 1
+            );
+            height += 1;
+        }
+
+        Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        }
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if `pos` is inside of the grid.
+    #[must_use]
+    pub fn contains(&self, pos: IVec2) -> bool {
+        self.index(pos).is_some()
+    }
+
+    fn index(&self, pos: IVec2) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn pos(&self, index: usize) -> IVec2 {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let pos = IVec2::new((index % self.width) as i32, (index / self.width) as i32);
+        pos
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: IVec2) -> Option<&T> {
+        self.index(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut T> {
+        self.index(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// Iterates over all positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = IVec2> {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        let (width, height) = (self.width as i32, self.height as i32);
+        (0..height).flat_map(move |y| (0..width).map(move |x| IVec2::new(x, y)))
+    }
+
+    /// Iterates over all cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (IVec2, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| (self.pos(index), cell))
+    }
+
+    /// Returns the position of the first cell that matches `predicate`, row by row.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<IVec2> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|index| self.pos(index))
+    }
+
+    /// Iterates over the orthogonal neighbours of `pos` that are inside of the grid, clockwise starting north.
+    pub fn neighbours4(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBOURS4
+            .into_iter()
+            .map(move |offset| pos + offset)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Iterates over the orthogonal and diagonal neighbours of `pos` that are inside of the grid,
+    /// clockwise starting north.
+    pub fn neighbours8(&self, pos: IVec2) -> impl Iterator<Item = IVec2> + '_ {
+        NEIGHBOURS8
+            .into_iter()
+            .map(move |offset| pos + offset)
+            .filter(|neighbour| self.contains(*neighbour))
+    }
+
+    /// Returns the cells of row `y`.
+    ///
+    /// # Panics
+    /// Panics if `y` is outside of the grid.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside of the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates over the cells of column `x`, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `x` is outside of the grid.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Iterates over the rows, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// Iterates over the columns, from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Creates a grid of the same size with every cell mapped by `f`.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flatten().cloned().collect();
+        Self::from_cells(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by a quarter turn clockwise.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flat_map(Iterator::rev).cloned().collect();
+        Self::from_cells(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by a quarter turn counterclockwise.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x))
+            .cloned()
+            .collect();
+        Self::from_cells(self.height, self.width, cells)
+    }
+
+    /// Renders the grid with one character per cell and one line per row.
+    #[must_use]
+    pub fn to_string_with(&self, mut char: impl FnMut(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.iter().map(&mut char));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<IVec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: IVec2) -> &T {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "{pos} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<IVec2> for Grid<T> {
+    fn index_mut(&mut self, pos: IVec2) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{pos} is outside of the {width}x{height} grid"))
+    }
+}
+
+/// Prints every cell and a line break after every row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Grid;
+    use glam::IVec2;
+
+    #[test]
+    fn parses_text() {
+        let grid = Grid::parse("123\n456\n\n", |c| c.to_digit(10).unwrap());
+
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[IVec2::new(2, 0)], 3);
+        assert_eq!(grid.get(IVec2::new(0, 1)), Some(&4));
+        assert_eq!(grid.get(IVec2::new(3, 0)), None);
+        assert_eq!(grid.get(IVec2::new(0, -1)), None);
+        assert_eq!(grid.position(|cell| *cell == 5), Some(IVec2::new(1, 1)));
+        assert_eq!(grid.to_string(), "123\n456\n");
+        assert_eq!(Grid::parse("", |c| c), Grid::from_cells(0, 0, vec![]));
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2 should have 3 characters")]
+    fn rejects_ragged_lines() {
+        let _ = Grid::parse("...\n..\n", |c| c);
+    }
+
+    #[test]
+    fn writes_cells() {
+        let mut grid = Grid::filled(2, 2, '.');
+        grid[IVec2::new(1, 0)] = '#';
+        *grid.get_mut(IVec2::new(0, 1)).unwrap() = '#';
+
+        assert_eq!(grid.get_mut(IVec2::new(2, 2)), None);
+        assert_eq!(grid.to_string(), ".#\n#.\n");
+        assert_eq!(
+            grid.map(|c| *c == '#').to_string(),
+            "falsetrue\ntruefalse\n"
+        );
+    }
+
+    #[test]
+    fn finds_neighbours() {
+        let grid = Grid::filled(3, 3, ());
+
+        assert_eq!(
+            grid.neighbours4(IVec2::ZERO).collect::<Vec<_>>(),
+            [IVec2::new(1, 0), IVec2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbours4(IVec2::ONE).count(), 4);
+        assert_eq!(grid.neighbours8(IVec2::new(2, 1)).count(), 5);
+        assert_eq!(grid.neighbours8(IVec2::ONE).count(), 8);
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = Grid::parse("ab\ncd\nef", |c| c);
+
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b'], ['c', 'd'], ['e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ace", "bdf"]
+        );
+        assert_eq!(grid.iter().nth(3), Some((IVec2::new(1, 1), &'d')));
+        assert_eq!(grid.positions().last(), Some(IVec2::new(1, 2)));
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = Grid::parse("ab\ncd\nef", |c| c);
+
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "bdf\nace\n");
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        assert_eq!(
+            (0..4).fold(grid.clone(), |grid, _| grid.rotate_clockwise()),
+            grid
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Sets of integers stored as half-open ranges, and maps that move ranges of integers around.
+//! Puzzles like day 5 work on ranges that are far too large to handle number by number, these types
+//! keep the work proportional to the number of ranges instead.
+
+use std::cmp::{max, min};
+use std::ops::{Add, Range, Sub};
+
+/// The integer types that can be used in an [`IntervalSet`] or a [`PiecewiseMap`].
+pub trait Integer: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T> Integer for T where T: Copy + Ord + Add<Output = T> + Sub<Output = T> {}
+
+/// A set of integers, stored as sorted half-open ranges that neither overlap nor touch.
+/// Because every set has exactly one such representation, two sets are equal if their ranges are.
+///
+/// ```
+/// # use advent_of_code::intervals::IntervalSet;
+/// let set: IntervalSet<u32> = [5..8, 1..3, 2..5].into_iter().collect();
+/// assert_eq!(set.ranges(), [1..8]);
+/// assert_eq!(set.difference(&IntervalSet::from(3..4)).ranges(), [1..3, 4..8]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Sorts `ranges`, merges the ones that overlap or touch and drops empty ones.
+    #[must_use]
+    pub fn normalize(mut ranges: Vec<Range<T>>) -> Self {
+        ranges.retain(|range| !range.is_empty());
+        ranges.sort_unstable_by_key(|range| range.start);
+
+        let mut normalized: Vec<Range<T>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match normalized.last_mut() {
+                Some(last) if range.start <= last.end => last.end = max(last.end, range.end),
+                _ => normalized.push(range),
+            }
+        }
+        Self { ranges: normalized }
+    }
+
+    /// The ranges of the set, in ascending order.
+    #[must_use]
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    #[must_use]
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(&value))
+    }
+
+    /// The smallest number in the set.
+    #[must_use]
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        while let (Some(range_a), Some(range_b)) = (a.peek(), b.peek()) {
+            let start = max(range_a.start, range_b.start);
+            let end = min(range_a.end, range_b.end);
+            if start < end {
+                ranges.push(start..end);
+            }
+            // the range that ends first can't overlap anything else of the other set.
+            if range_a.end <= range_b.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The numbers of `self` that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+            while let Some(other) = others.peek() {
+                if other.end <= start {
+                    others.next();
+                    continue;
+                }
+                if other.start >= range.end {
+                    break;
+                }
+                if start < other.start {
+                    ranges.push(start..other.start);
+                }
+                start = other.end;
+                if other.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+        Self { ranges }
+    }
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::normalize(vec![range])
+    }
+}
+
+impl<T: Integer> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+/// A range of numbers that is moved to `destination`, keeping the order of the numbers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Segment<T> {
+    source: Range<T>,
+    destination: T,
+}
+
+impl<T: Integer> Segment<T> {
+    fn apply(&self, value: T) -> T {
+        self.destination + (value - self.source.start)
+    }
+
+    fn image(&self) -> Range<T> {
+        self.destination..self.apply(self.source.end)
+    }
+}
+
+/// A function on integers that moves ranges of numbers and keeps all other numbers where they are,
+/// like the maps of the almanac on day 5.
+///
+/// ```
+/// # use advent_of_code::intervals::{IntervalSet, PiecewiseMap};
+/// let map = PiecewiseMap::new([(98..100, 50), (50..98, 52)]);
+/// assert_eq!(map.apply(99), 51);
+/// assert_eq!(map.apply(10), 10);
+/// assert_eq!(map.apply_set(&IntervalSet::from(96..100)).ranges(), [50..52, 98..100]);
+/// assert_eq!(map.compose(&map.inverse().unwrap()), PiecewiseMap::identity());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PiecewiseMap<T> {
+    // Sorted by source and without segments that map to themselves, adjacent segments with the same offset
+    // are merged. Like `IntervalSet`, every map has exactly one representation.
+    segments: Vec<Segment<T>>,
+}
+
+impl<T: Integer> PiecewiseMap<T> {
+    /// The map that keeps every number where it is.
+    #[must_use]
+    pub fn identity() -> Self {
+        Self {
+            segments: Vec::new(),
+        }
+    }
+
+    /// Creates a map that moves every `source` range to start at its `destination`.
+    ///
+    /// # Panics
+    /// Panics if the source ranges overlap.
+    pub fn new(segments: impl IntoIterator<Item = (Range<T>, T)>) -> Self {
+        let mut segments: Vec<Segment<T>> = segments
+            .into_iter()
+            .filter(|(source, destination)| !source.is_empty() && source.start != *destination)
+            .map(|(source, destination)| Segment {
+                source,
+                destination,
+            })
+            .collect();
+        segments.sort_unstable_by_key(|segment| segment.source.start);
+
+        let mut merged: Vec<Segment<T>> = Vec::with_capacity(segments.len());
+        for segment in segments {
+            match merged.last_mut() {
+                Some(last) if segment.source.start < last.source.end => {
+                    panic!("the sources of a piecewise map should not overlap")
+                }
+                Some(last)
+                    if segment.source.start == last.source.end
+                        && segment.destination == last.apply(last.source.end) =>
+                {
+                    last.source.end = segment.source.end;
+                }
+                _ => merged.push(segment),
+            }
+        }
+        Self { segments: merged }
+    }
+
+    #[must_use]
+    pub fn apply(&self, value: T) -> T {
+        let index = self
+            .segments
+            .partition_point(|segment| segment.source.end <= value);
+        match self.segments.get(index) {
+            Some(segment) if segment.source.contains(&value) => segment.apply(value),
+            _ => value,
+        }
+    }
+
+    /// Maps every number of `set`.
+    #[must_use]
+    pub fn apply_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|range| self.pieces(range.clone()))
+            .map(|(source, destination)| destination..destination + (source.end - source.start))
+            .collect()
+    }
+
+    /// The numbers that the map moves.
+    #[must_use]
+    pub fn sources(&self) -> IntervalSet<T> {
+        self.segments
+            .iter()
+            .map(|segment| segment.source.clone())
+            .collect()
+    }
+
+    /// The map that applies `self` and then `then`.
+    #[must_use]
+    pub fn compose(&self, then: &Self) -> Self {
+        let mut segments = Vec::new();
+
+        for segment in &self.segments {
+            for (image, destination) in then.pieces(segment.image()) {
+                let start = segment.source.start + (image.start - segment.destination);
+                segments.push((start..start + (image.end - image.start), destination));
+            }
+        }
+
+        // `self` keeps everything outside of its sources, there only `then` moves numbers.
+        let sources = self.sources();
+        for segment in &then.segments {
+            let outside = IntervalSet::from(segment.source.clone()).difference(&sources);
+            for range in outside.ranges() {
+                segments.push((range.clone(), segment.apply(range.start)));
+            }
+        }
+
+        Self::new(segments)
+    }
+
+    /// The map that undoes `self`, if no two numbers are mapped to the same number.
+    #[must_use]
+    pub fn inverse(&self) -> Option<Self> {
+        // The sources don't overlap, so the images only cover the sources if they don't overlap either.
+        let images: IntervalSet<T> = self.segments.iter().map(Segment::image).collect();
+        (images == self.sources()).then(|| {
+            Self::new(
+                self.segments
+                    .iter()
+                    .map(|segment| (segment.image(), segment.source.start)),
+            )
+        })
+    }
+
+    /// Splits `range` at the borders of the segments, with the number that every piece starts at after mapping.
+    fn pieces(&self, range: Range<T>) -> Vec<(Range<T>, T)> {
+        let mut pieces = Vec::new();
+        if range.is_empty() {
+            return pieces;
+        }
+
+        let first = self
+            .segments
+            .partition_point(|segment| segment.source.end <= range.start);
+        let mut start = range.start;
+        for segment in self.segments[first..]
+            .iter()
+            .take_while(|segment| segment.source.start < range.end)
+        {
+            if start < segment.source.start {
+                pieces.push((start..segment.source.start, start));
+                start = segment.source.start;
+            }
+            let end = min(segment.source.end, range.end);
+            pieces.push((start..end, segment.apply(start)));
+            start = end;
+        }
+        if start < range.end {
+            pieces.push((start..range.end, start));
+        }
+        pieces
+    }
+}
+
+impl<T: Integer> Default for PiecewiseMap<T> {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{IntervalSet, PiecewiseMap};
+
+    fn set(ranges: &[std::ops::Range<i32>]) -> IntervalSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    fn set_u64(ranges: &[std::ops::Range<u64>]) -> IntervalSet<u64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn normalizes() {
+        let set = IntervalSet::normalize(vec![8..9, 0..2, 4..4, 1..3, 3..5]);
+
+        assert_eq!(set.ranges(), [0..5, 8..9]);
+        assert!(set.contains(4));
+        assert!(!set.contains(5));
+        assert!(!set.contains(-1));
+        assert_eq!(set.min(), Some(0));
+        assert!(IntervalSet::<u8>::from(3..3).is_empty());
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a = set(&[0..5, 10..15, 20..25]);
+        let b = set(&[3..12, 14..21, 30..31]);
+
+        assert_eq!(a.union(&b), set(&[0..25, 30..31]));
+        assert_eq!(a.intersection(&b), set(&[3..5, 10..12, 14..15, 20..21]));
+        assert_eq!(a.difference(&b), set(&[0..3, 12..14, 21..25]));
+        assert_eq!(b.difference(&a), set(&[5..10, 15..20, 30..31]));
+        assert_eq!(a.difference(&set(&[-5..30])), IntervalSet::new());
+        assert_eq!(a.difference(&IntervalSet::new()), a);
+    }
+
+    #[test]
+    fn touching_ranges_do_not_intersect() {
+        let a = set(&[0..5]);
+        let b = set(&[5..10]);
+
+        assert!(a.intersection(&b).is_empty());
+        assert_eq!(a.difference(&b), a);
+        assert_eq!(a.union(&b), set(&[0..10]));
+    }
+
+    #[test]
+    fn applies_map() {
+        let map = PiecewiseMap::new([(98..100, 50), (50..98, 52)]);
+
+        assert_eq!(
+            [0, 50, 97, 98, 99, 100].map(|n| map.apply(n)),
+            [0, 52, 99, 50, 51, 100]
+        );
+        assert_eq!(
+            map.apply_set(&IntervalSet::from(40u64..60)),
+            set_u64(&[40..50, 52..62])
+        );
+        // the end of a range is not part of it, so a range that ends where a segment starts stays in place.
+        assert_eq!(
+            map.apply_set(&IntervalSet::from(45..50)),
+            set_u64(&[45..50])
+        );
+        assert_eq!(
+            map.apply_set(&IntervalSet::from(100..102)),
+            set_u64(&[100..102])
+        );
+    }
+
+    #[test]
+    fn normalizes_maps() {
+        assert_eq!(
+            PiecewiseMap::new([(0..5, 10), (5..8, 15), (20..22, 20)]),
+            PiecewiseMap::new([(0..8, 10)])
+        );
+        assert_eq!(PiecewiseMap::new([(3..9, 3)]), PiecewiseMap::identity());
+    }
+
+    #[test]
+    #[should_panic(expected = "should not overlap")]
+    fn rejects_overlapping_sources() {
+        let _ = PiecewiseMap::new([(0..5, 10), (4..6, 20)]);
+    }
+
+    #[test]
+    fn composes_maps() {
+        let first = PiecewiseMap::new([(98..100, 50), (50..98, 52)]);
+        let second = PiecewiseMap::new([(15..52, 0), (52..54, 37), (0..15, 39)]);
+        let composed = first.compose(&second);
+
+        for n in 0..120 {
+            assert_eq!(composed.apply(n), second.apply(first.apply(n)), "{n}");
+        }
+        let seeds = set_u64(&[55..68, 79..93]);
+        assert_eq!(
+            composed.apply_set(&seeds),
+            second.apply_set(&first.apply_set(&seeds))
+        );
+        assert_eq!(first.compose(&PiecewiseMap::identity()), first);
+        assert_eq!(PiecewiseMap::identity().compose(&first), first);
+    }
+
+    #[test]
+    fn inverts_maps() {
+        let map = PiecewiseMap::new([(-10..-5, 5), (0..5, -10), (5..10, 0)]);
+        let inverse = map.inverse().unwrap();
+
+        for n in -20..20 {
+            assert_eq!(inverse.apply(map.apply(n)), n);
+        }
+        assert_eq!(inverse.inverse(), Some(map.clone()));
+        assert_eq!(map.compose(&inverse), PiecewiseMap::identity());
+        assert_eq!(PiecewiseMap::new([(0..5, 10)]).inverse(), None);
+        assert_eq!(PiecewiseMap::new([(0..5, 10), (10..12, 0)]).inverse(), None);
+    }
+}
@@ -1,4 +1,10 @@
 mod day;
+pub mod grid;
+pub mod intervals;
+pub mod ocr;
+pub mod rng;
+pub mod span;
 pub mod template;
+pub mod visualize;
 
 pub use day::*;
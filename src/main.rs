@@ -1,18 +1,39 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use std::process::ExitCode;
+
+use advent_of_code::template::commands::{
+    all, calendar, check_ref, completions, download, generate, leaderboard, progress, read,
+    scaffold, solve, status, test_day,
+};
+use advent_of_code::template::error::{self, Error};
+use advent_of_code::template::outcome::Outcome;
+use advent_of_code::template::{cli, config};
 use args::{parse, AppArguments};
 
 mod args {
-    use std::process;
+    use std::{env, ffi::OsString, fmt::Display, path::PathBuf, str::FromStr, time::Duration};
 
+    use advent_of_code::template::cli::{self, Command};
+    use advent_of_code::template::completions::Shell;
+    use advent_of_code::template::config::{self, parse_duration};
+    use advent_of_code::template::day_set::DaySet;
+    use advent_of_code::template::trace;
     use advent_of_code::Day;
 
     pub enum AppArguments {
+        Help {
+            command: Option<&'static Command>,
+        },
         Download {
-            day: Day,
+            days: DaySet,
         },
         Read {
             day: Day,
         },
+        Leaderboard {
+            id: String,
+            offline: bool,
+            file: Option<PathBuf>,
+        },
         Scaffold {
             day: Day,
         },
@@ -21,72 +42,381 @@ mod args {
             release: bool,
             time: bool,
             submit: Option<u8>,
+            timeout: Option<Duration>,
+            trace: Option<String>,
+            trace_out: Option<PathBuf>,
+            trace_folded: Option<PathBuf>,
+            loop_part: Option<u8>,
+            duration: Option<Duration>,
+            visualize: Option<PathBuf>,
+            variants: bool,
+        },
+        Status {
+            days: DaySet,
+            json: bool,
+            tests: bool,
+        },
+        Calendar {
+            plain: bool,
+        },
+        Progress,
+        Completions {
+            shell: Shell,
+        },
+        TestDay {
+            days: DaySet,
+        },
+        Generate {
+            day: Day,
+            size: usize,
+            seed: u64,
+            out: Option<PathBuf>,
+        },
+        CheckRef {
+            day: Day,
+            release: bool,
         },
         All {
+            days: DaySet,
             release: bool,
             time: bool,
+            timeout: Option<Duration>,
+            fail_fast: bool,
+            allow_partial: bool,
         },
     }
 
+    /// Global flags that override a config setting, with the environment variable they set.
+    /// Environment variables are inherited by solution binaries, so the overrides apply to them too.
+    pub(crate) const CONFIG_FLAGS: [(&str, &str); 7] = [
+        ("--config", config::CONFIG_PATH_VAR),
+        ("--year", "AOC_YEAR"),
+        ("--inputs-dir", "AOC_INPUTS_DIR"),
+        ("--examples-dir", "AOC_EXAMPLES_DIR"),
+        ("--puzzles-dir", "AOC_PUZZLES_DIR"),
+        ("--answers-dir", "AOC_ANSWERS_DIR"),
+        ("--bin-dir", "AOC_BIN_DIR"),
+    ];
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        let help = args.contains(["-h", "--help"]);
+        let command = match args.subcommand()? {
+            Some(name) => cli::find(&name).ok_or_else(|| unknown_command(&name))?,
+            None if help => return Ok(AppArguments::Help { command: None }),
+            None => return Err("no command specified, run with `--help` to list them.".into()),
+        };
+
+        if help {
+            return Ok(AppArguments::Help {
+                command: Some(command),
+            });
+        }
+
+        let mut trace = None;
+        if command.name == "solve" {
+            let mut remaining = args.finish();
+            trace = take_trace(&mut remaining);
+            args = pico_args::Arguments::from_vec(remaining);
+        }
+
+        for (flag, var) in CONFIG_FLAGS {
+            if let Some(value) = args.opt_value_from_str::<_, String>(flag)? {
+                env::set_var(var, value);
+            }
+        }
+
+        // Free arguments are taken last, after all known flags are gone.
+        let app_args = match command.name {
+            "all" => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                fail_fast: args.contains("--fail-fast"),
+                allow_partial: args.contains("--allow-partial"),
+                days: opt_free_from_str(&mut args, command)?.unwrap_or_default(),
+            },
+            "status" => AppArguments::Status {
+                json: args.contains("--json"),
+                tests: !args.contains("--no-tests"),
+                days: opt_free_from_str(&mut args, command)?.unwrap_or_default(),
+            },
+            "gen" => AppArguments::Generate {
+                size: args
+                    .opt_value_from_str("--size")?
+                    .ok_or("missing `--size <n>` for `gen`, see `cargo gen --help`.")?,
+                seed: args.opt_value_from_str("--seed")?.unwrap_or_default(),
+                out: args.opt_value_from_str("--out")?,
+                day: free_from_str(&mut args, command)?,
+            },
+            "check-ref" => AppArguments::CheckRef {
+                release: args.contains("--release"),
+                day: free_from_str(&mut args, command)?,
+            },
+            "test-day" => AppArguments::TestDay {
+                days: free_from_str(&mut args, command)?,
+            },
+            "calendar" => AppArguments::Calendar {
+                plain: args.contains("--plain"),
             },
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "progress" => AppArguments::Progress,
+            "completions" => AppArguments::Completions {
+                shell: free_from_str(&mut args, command)?,
             },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+            "download" => AppArguments::Download {
+                days: free_from_str(&mut args, command)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+            "read" => AppArguments::Read {
+                day: free_from_str(&mut args, command)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            "leaderboard" => AppArguments::Leaderboard {
+                offline: args.contains("--offline"),
+                file: args.opt_value_from_str("--file")?,
+                id: free_from_str(&mut args, command)?,
+            },
+            "scaffold" => AppArguments::Scaffold {
+                day: free_from_str(&mut args, command)?,
+            },
+            "solve" => AppArguments::Solve {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+                trace,
+                trace_out: args.opt_value_from_str("--trace-out")?,
+                trace_folded: args.opt_value_from_str("--trace-folded")?,
+                loop_part: args.opt_value_from_str("--loop-part")?,
+                duration: args.opt_value_from_fn("--duration", parse_duration)?,
+                visualize: args.opt_value_from_str("--visualize")?,
+                variants: args.contains("--variants"),
+                day: free_from_str(&mut args, command)?,
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            name => return Err(unknown_command(name).into()),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
+        cli::check_unused(command, &to_strings(args.finish()))?;
 
         Ok(app_args)
     }
+
+    /// Parses the free argument of a command. Unknown flags are reported first,
+    /// otherwise a typo like `--tme` would be parsed as the free argument.
+    fn free_from_str<T>(
+        args: &mut pico_args::Arguments,
+        command: &Command,
+    ) -> Result<T, Box<dyn std::error::Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        opt_free_from_str(args, command)?.ok_or_else(|| {
+            format!(
+                "missing {} for `{}`, see `cargo {} --help`.",
+                command
+                    .positional
+                    .map_or_else(|| "argument".into(), |value| value.placeholder()),
+                command.name,
+                command.name
+            )
+            .into()
+        })
+    }
+
+    /// Parses the free argument of a command if it is present, see [`free_from_str`].
+    fn opt_free_from_str<T>(
+        args: &mut pico_args::Arguments,
+        command: &Command,
+    ) -> Result<Option<T>, Box<dyn std::error::Error>>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let flags: Vec<String> = to_strings(args.clone().finish())
+            .into_iter()
+            .filter(|arg| arg.starts_with('-'))
+            .collect();
+        cli::check_unused(command, &flags)?;
+
+        Ok(args.opt_free_from_str()?)
+    }
+
+    fn to_strings(args: Vec<OsString>) -> Vec<String> {
+        args.iter()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn unknown_command(name: &str) -> String {
+        let names = cli::COMMANDS.iter().map(|command| command.name);
+        match cli::suggest(name, names) {
+            Some(suggestion) => {
+                format!("unknown command \"{name}\", did you mean \"{suggestion}\"?")
+            }
+            None => format!("unknown command \"{name}\", run with `--help` to list the commands."),
+        }
+    }
+
+    /// Removes `--trace [filter]` from the arguments. As the filter is optional,
+    /// the next argument is only taken as filter if it is neither a flag nor a day.
+    fn take_trace(args: &mut Vec<OsString>) -> Option<String> {
+        let index = args.iter().position(|arg| arg == "--trace")?;
+        args.remove(index);
+
+        let is_filter = args
+            .get(index)
+            .and_then(|arg| arg.to_str())
+            .is_some_and(|arg| !arg.starts_with('-') && arg.parse::<Day>().is_err());
+
+        if is_filter {
+            args.remove(index).into_string().ok()
+        } else {
+            Some(trace::default_filter())
+        }
+    }
 }
 
-fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+fn run(args: AppArguments) -> Result<Outcome, Error> {
+    if let AppArguments::Help { command } = args {
+        print!("{}", cli::help(command));
+        return Ok(Outcome::Success);
+    }
+
+    let defaults = &config::init()?.defaults;
+
+    match args {
+        AppArguments::Help { .. } => unreachable!("help is printed above"),
+        AppArguments::All {
+            days,
+            release,
+            time,
+            timeout,
+            fail_fast,
+            allow_partial,
+        } => all::handle(
+            &days.days(),
+            release || defaults.release,
+            time || defaults.time,
+            timeout.or(defaults.timeout),
+            fail_fast,
+            allow_partial,
+        ),
+        AppArguments::Status { days, json, tests } => {
+            status::handle(&days.days(), json, tests).map(|()| Outcome::Success)
+        }
+        AppArguments::TestDay { days } => test_day::handle(&days.days()),
+        AppArguments::Generate {
+            day,
+            size,
+            seed,
+            out,
+        } => generate::handle(day, size, seed, out),
+        AppArguments::CheckRef { day, release } => check_ref::handle(day, release),
+        AppArguments::Calendar { plain } => {
+            calendar::handle(plain);
+            Ok(Outcome::Success)
+        }
+        AppArguments::Progress => progress::handle().map(|()| Outcome::Success),
+        AppArguments::Completions { shell } => {
+            completions::handle(shell);
+            Ok(Outcome::Success)
+        }
+        AppArguments::Download { days } => {
+            download::handle(&days.days()).map(|()| Outcome::Success)
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
+        AppArguments::Read { day } => read::handle(day).map(|()| Outcome::Success),
+        AppArguments::Leaderboard { id, offline, file } => {
+            let source = match (&file, offline) {
+                (Some(file), _) => leaderboard::Source::File(file),
+                (None, true) => leaderboard::Source::Cache,
+                (None, false) => leaderboard::Source::Fetch,
+            };
+            leaderboard::handle(&id, source).map(|()| Outcome::Success)
+        }
+        AppArguments::Scaffold { day } => scaffold::handle(day).map(|()| Outcome::Success),
+        AppArguments::Solve {
+            day,
+            release,
+            time,
+            submit,
+            timeout,
+            trace,
+            trace_out,
+            trace_folded,
+            loop_part,
+            duration,
+            visualize,
+            variants,
+        } => {
+            if duration.is_some() && loop_part.is_none() {
+                return Err(Error::InvalidArguments(
+                    "`--duration` only applies to `--loop-part <part>`.".into(),
+                ));
+            }
+
+            let mut modes: Vec<solve::Mode> = [
+                loop_part.map(|part| solve::Mode::Loop { part, duration }),
+                visualize.map(solve::Mode::Visualize),
+                variants.then_some(solve::Mode::Variants),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            if modes.len() > 1 {
+                return Err(Error::InvalidArguments(
+                    "only one of `--loop-part`, `--visualize` and `--variants` can be used at a time."
+                        .into(),
+                ));
+            }
+            let mode = modes.pop().unwrap_or_default();
+            let is_looping = matches!(mode, solve::Mode::Loop { .. });
+
+            solve::handle(
                 day,
-                release,
-                time,
+                release || defaults.release,
+                time || defaults.time,
                 submit,
-            } => solve::handle(day, release, time, submit),
-        },
-    };
+                // the configured timeout is meant for regular runs, not for looping.
+                timeout.or(defaults.timeout.filter(|_| !is_looping)),
+                &solve::TraceOptions {
+                    filter: trace,
+                    out: trace_out,
+                    folded: trace_folded,
+                },
+                &mode,
+            )
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let result = parse()
+        .map_err(|err| Error::InvalidArguments(err.to_string()))
+        .and_then(run);
+
+    match result {
+        Ok(outcome) => outcome.into(),
+        Err(err) => {
+            error::report(&err);
+            Outcome::Error.into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use advent_of_code::template::cli;
+
+    #[test]
+    fn config_flags_match_cli() {
+        let names: Vec<String> = cli::GLOBAL_FLAGS
+            .iter()
+            .map(|flag| format!("--{}", flag.name))
+            .collect();
+        let config_flags: Vec<&str> = super::args::CONFIG_FLAGS
+            .iter()
+            .map(|(flag, _)| *flag)
+            .collect();
+        assert_eq!(names, config_flags);
+    }
 }
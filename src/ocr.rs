@@ -0,0 +1,249 @@
+//! Recognises answers that are drawn as block letters, e.g. on a CRT screen or a folded sheet of paper.
+//! Advent of Code uses two fonts for these puzzles: a small one with 6 rows per glyph and a large one with 10 rows per glyph.
+//! Glyphs are separated by at least one empty column, which is used to split the drawing into letters.
+
+/// Glyphs of the small font (6 rows high, usually 4 columns wide).
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Glyphs of the large font (10 rows high, 6 columns wide).
+const LARGE_FONT: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// Returns `true` if a character of a drawing represents a lit pixel.
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Decodes a drawing made of `#` (lit) and `.` (unlit) characters into the letters it shows.
+/// `█` is accepted as a lit pixel and any other character as an unlit pixel.
+/// Leading and trailing lines without lit pixels are ignored.
+/// Returns [`None`] if the drawing does not have the height of a known font or contains an unknown glyph.
+///
+/// ```
+/// let art = [
+///     "#..#.###.",
+///     "#..#..#..",
+///     "####..#..",
+///     "#..#..#..",
+///     "#..#..#..",
+///     "#..#.###.",
+/// ];
+/// assert_eq!(advent_of_code::ocr::parse(&art.join("\n")), Some("HI".into()));
+/// ```
+#[must_use]
+pub fn parse(art: &str) -> Option<String> {
+    let grid: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+
+    let has_lit_pixel = |row: &Vec<bool>| row.contains(&true);
+    let start = grid.iter().position(has_lit_pixel).unwrap_or(0);
+    let end = grid
+        .iter()
+        .rposition(has_lit_pixel)
+        .map_or(0, |index| index + 1);
+
+    parse_grid(&grid[start..end.max(start)])
+}
+
+/// Decodes a grid of pixels (`true` = lit) into the letters it shows.
+/// Rows may have different lengths, missing pixels count as unlit.
+/// Returns [`None`] if the grid does not have the height of a known font or contains an unknown glyph.
+#[must_use]
+pub fn parse_grid(grid: &[Vec<bool>]) -> Option<String> {
+    let font: &[(char, &str)] = match grid.len() {
+        6 => &SMALL_FONT,
+        10 => &LARGE_FONT,
+        _ => return None,
+    };
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let is_lit_at = |x: usize, y: usize| grid[y].get(x).copied().unwrap_or(false);
+    let is_blank_column = |x: usize| (0..grid.len()).all(|y| !is_lit_at(x, y));
+
+    let mut letters = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_blank_column(x) {
+            x += 1;
+        }
+
+        let glyph = (0..grid.len())
+            .map(|y| {
+                (start..x)
+                    .map(|x| if is_lit_at(x, y) { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        let (letter, _) = font.iter().find(|(_, pattern)| *pattern == glyph)?;
+        letters.push(*letter);
+    }
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, parse_grid, LARGE_FONT, SMALL_FONT};
+
+    #[test]
+    fn parses_every_small_glyph() {
+        for (letter, pattern) in SMALL_FONT {
+            assert_eq!(parse(pattern), Some(letter.to_string()));
+        }
+    }
+
+    #[test]
+    fn parses_every_large_glyph() {
+        for (letter, pattern) in LARGE_FONT {
+            assert_eq!(parse(pattern), Some(letter.to_string()));
+        }
+    }
+
+    /// Draws `letters` with `font`, separating glyphs by `gap` empty columns.
+    fn draw(font: &[(char, &str)], letters: &str, gap: usize) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|letter| {
+                let (_, pattern) = font.iter().find(|(c, _)| *c == letter).unwrap();
+                pattern.lines().collect()
+            })
+            .collect();
+
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|glyph| glyph[y])
+                    .collect::<Vec<&str>>()
+                    .join(&".".repeat(gap))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn parses_small_word() {
+        let art = draw(&SMALL_FONT, "EUBUERUL", 1);
+        assert_eq!(parse(&art), Some("EUBUERUL".into()));
+    }
+
+    #[test]
+    fn parses_large_word_with_wide_gaps() {
+        let art = draw(&LARGE_FONT, "XENZ", 2);
+        assert_eq!(parse(&art), Some("XENZ".into()));
+    }
+
+    #[test]
+    fn parses_block_characters_and_surrounding_blank_lines() {
+        let art = format!("\n{}\n\n", draw(&SMALL_FONT, "HI", 1))
+            .replace('#', "█")
+            .replace('.', " ");
+        assert_eq!(parse(&art), Some("HI".into()));
+    }
+
+    #[test]
+    fn parses_bool_grid() {
+        let grid: Vec<Vec<bool>> = ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        assert_eq!(parse_grid(&grid), Some("H".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs_and_heights() {
+        assert_eq!(parse("#\n#\n#"), None);
+        assert_eq!(parse("##\n##\n##\n##\n##\n##"), None);
+        assert_eq!(parse(""), None);
+    }
+}
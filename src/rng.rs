@@ -0,0 +1,149 @@
+//! A small deterministic random number generator for generating puzzle inputs.
+//! The same seed always produces the same numbers, on every platform and with every version of this template,
+//! so a generated input can be shared as `--size` and `--seed` instead of as a file.
+//! It is based on SplitMix64, which is fast and good enough for test data, but not suitable for cryptography.
+
+use std::ops::Range;
+
+/// A seeded pseudo random number generator.
+///
+/// ```
+/// # use advent_of_code::rng::Rng;
+/// let mut rng = Rng::new(42);
+/// let roll = rng.range(1..7);
+/// assert!((1..7).contains(&roll));
+/// assert_eq!(Rng::new(42).range(1..7), roll);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    #[must_use]
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `0..n`.
+    ///
+    /// # Panics
+    /// Panics if `n` is 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "cannot pick a number below 0");
+        // Lemire's multiply-shift, the bias is negligible for test data.
+        #[allow(clippy::cast_possible_truncation)]
+        let value = ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64;
+        value
+    }
+
+    /// Returns a number in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            !range.is_empty(),
+            "cannot pick a number from an empty range"
+        );
+        range.start + self.below(range.end - range.start)
+    }
+
+    /// Returns an index in `0..len`.
+    ///
+    /// # Panics
+    /// Panics if `len` is 0.
+    pub fn index(&mut self, len: usize) -> usize {
+        #[allow(clippy::cast_possible_truncation)]
+        let index = self.below(len as u64) as usize;
+        index
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is 0.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        assert!(denominator > 0, "cannot pick a chance out of 0");
+        self.below(denominator) < numerator
+    }
+
+    /// Returns a random element of `items`.
+    ///
+    /// # Panics
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    /// Shuffles `items` in place (Fisher-Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn is_deterministic() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..4).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        // reference values of SplitMix64, which must not change between versions.
+        assert_eq!(
+            numbers(0)[..2],
+            [0xe220_a839_7b1d_cdaf, 0x6e78_9e6a_a1b9_65f4]
+        );
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let value = rng.range(10..16);
+            assert!((10..16).contains(&value));
+            seen[(value - 10) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|seen| *seen));
+        assert_eq!(rng.below(1), 0);
+    }
+
+    #[test]
+    fn shuffles_all_items() {
+        let mut rng = Rng::new(3);
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+
+        assert_ne!(items, (0..50).collect::<Vec<_>>());
+        items.sort_unstable();
+        assert_eq!(items, (0..50).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn chooses_with_chance() {
+        let mut rng = Rng::new(5);
+        assert!((0..100).all(|_| rng.chance(1, 1)));
+        assert!((0..100).all(|_| !rng.chance(0, 1)));
+        assert_eq!(*rng.choose(&['x']), 'x');
+    }
+}
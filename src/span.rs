@@ -0,0 +1,156 @@
+//! Helpers to parse grids of tokens with [`nom_locate`], where every token keeps the position it was found at.
+//! A [`Span`] tracks its line and column while it is parsed, [`with_xy`] turns that into an `IVec2` with `x` as the
+//! column and `y` as the row, starting at 0 like the [`Grid`](crate::grid::Grid).
+//!
+//! Puzzles where every character is a cell are simpler to parse with [`Grid::parse`](crate::grid::Grid::parse),
+//! these helpers are for tokens that span several characters, like numbers.
+
+use glam::IVec2;
+use nom::{
+    character::complete::anychar,
+    error::{ErrorKind, ParseError},
+    IResult, Parser,
+};
+use nom_locate::LocatedSpan;
+
+/// Input that tracks its position while it is parsed.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+/// A parsed token with its position as `extra`.
+pub type SpanIVec2<'a> = LocatedSpan<&'a str, IVec2>;
+
+/// The position of the first character of `span`.
+#[must_use]
+pub fn xy<X>(span: &LocatedSpan<&str, X>) -> IVec2 {
+    // Lines and columns are 1-indexed.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    let x = span.get_column() as i32 - 1;
+    #[allow(clippy::cast_possible_wrap)]
+    let y = span.location_line() as i32 - 1;
+    IVec2::new(x, y)
+}
+
+/// Stores the position of `span` in it.
+#[must_use]
+pub fn with_xy(span: Span) -> SpanIVec2 {
+    let pos = xy(&span);
+    span.map_extra(|()| pos)
+}
+
+/// Runs `parser` and stores the position of its output in it.
+pub fn located<'a, E, P>(
+    mut parser: P,
+) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, SpanIVec2<'a>, E>
+where
+    P: Parser<Span<'a>, Span<'a>, E>,
+{
+    move |input| {
+        parser
+            .parse(input)
+            .map(|(rest, span)| (rest, with_xy(span)))
+    }
+}
+
+/// Collects every `token` of the input, skipping the characters where it doesn't match.
+///
+/// ```
+/// # use advent_of_code::span::{grid_tokens, located, Span};
+/// # use nom::{character::complete::digit1, IResult};
+/// let result: IResult<_, _> = grid_tokens(located(digit1))(Span::new("..12\n3.."));
+/// let (_, numbers) = result.unwrap();
+/// assert_eq!(numbers.iter().map(|number| number.extra.to_array()).collect::<Vec<_>>(), [[2, 0], [0, 1]]);
+/// ```
+pub fn grid_tokens<'a, O, E, P>(
+    mut token: P,
+) -> impl FnMut(Span<'a>) -> IResult<Span<'a>, Vec<O>, E>
+where
+    P: Parser<Span<'a>, O, E>,
+    E: ParseError<Span<'a>>,
+{
+    move |mut input| {
+        let mut tokens = vec![];
+
+        while !input.fragment().is_empty() {
+            match token.parse(input) {
+                Ok((rest, output)) => {
+                    // Like `many0`, a token that doesn't consume anything would loop forever.
+                    if rest.location_offset() == input.location_offset() {
+                        let error = E::from_error_kind(input, ErrorKind::Many0);
+                        return Err(nom::Err::Error(error));
+                    }
+                    tokens.push(output);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => {
+                    (input, _) = anychar(input)?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((input, tokens))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{grid_tokens, located, with_xy, xy, Span};
+    use glam::IVec2;
+    use nom::{
+        branch::alt,
+        bytes::complete::{tag, take},
+        character::complete::{alpha1, digit1},
+        combinator::success,
+        IResult, Parser,
+    };
+
+    #[test]
+    fn finds_positions() {
+        let result: IResult<_, _> = take(5usize)(Span::new("ab\ncde"));
+        let (rest, _) = result.unwrap();
+
+        assert_eq!(xy(&Span::new("ab")), IVec2::ZERO);
+        assert_eq!(xy(&rest), IVec2::new(2, 1));
+        assert_eq!(with_xy(rest).extra, IVec2::new(2, 1));
+        assert_eq!(*with_xy(rest).fragment(), "e");
+    }
+
+    #[test]
+    fn collects_tokens() {
+        let result: IResult<_, _> = grid_tokens(alt((
+            located(digit1).map(|span| (span.extra, span.fragment().len())),
+            located(tag("*")).map(|span| (span.extra, 0)),
+        )))(Span::new("467..\n..*..\n.35#x\n"));
+        let (rest, tokens) = result.unwrap();
+
+        assert!(rest.fragment().is_empty());
+        assert_eq!(
+            tokens,
+            [
+                (IVec2::new(0, 0), 3),
+                (IVec2::new(2, 1), 0),
+                (IVec2::new(1, 2), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_tokens_with_same_characters_together() {
+        let result: IResult<_, _> = grid_tokens(located(alpha1))(Span::new("ab.c"));
+        let (_, tokens) = result.unwrap();
+
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|span| (*span.fragment(), span.extra.x))
+                .collect::<Vec<_>>(),
+            [("ab", 0), ("c", 3)]
+        );
+    }
+
+    #[test]
+    fn rejects_empty_tokens() {
+        let result: IResult<_, Vec<_>> = grid_tokens(success(()))(Span::new("ab"));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,199 @@
+/// Module that reads known answers for a day, used to detect regressions when re-running solutions.
+/// Answers live in `data/answers/DD.txt` (see [`config::Paths`]) and use the same format as the runner output, e.g.:
+/// ```text
+/// Part 1: 142
+/// Part 2: 281
+/// ```
+/// Lines that do not start with `Part N:` are ignored, and kept as they are when an answer is recorded. Answers drawn as letters are stored in their decoded form.
+///
+/// When a submitted answer is accepted, it is recorded in this file together with the number of earned stars (`Stars: 1`).
+use std::fmt::Display;
+use std::{fs, path::PathBuf};
+
+use crate::template::config;
+use crate::template::error::{Context, Error};
+use crate::Day;
+
+/// The known answers for both parts of a day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    /// The number of stars earned by submitting answers.
+    pub stars: u8,
+}
+
+impl Answers {
+    /// Returns the known answer for `part`, if any.
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Returns the number of parts with a known answer.
+    #[must_use]
+    pub fn count(&self) -> u8 {
+        u8::from(self.part_1.is_some()) + u8::from(self.part_2.is_some())
+    }
+
+    /// Returns the number of solved parts: earned stars, or parts with a known answer if there are more.
+    #[must_use]
+    pub fn progress(&self) -> u8 {
+        self.stars.max(self.count())
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (part, answer) in [(1, &self.part_1), (2, &self.part_2)] {
+            if let Some(answer) = answer {
+                writeln!(f, "Part {part}: {answer}")?;
+            }
+        }
+
+        if self.stars > 0 {
+            writeln!(f, "Stars: {}", self.stars)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn get_path_for_answers(day: Day) -> PathBuf {
+    config::get().paths.answers(day)
+}
+
+/// Reads the known answers for a day. Returns empty [`Answers`] if there is no answers file.
+#[must_use]
+pub fn read(day: Day) -> Answers {
+    fs::read_to_string(get_path_for_answers(day))
+        .map(|content| parse(&content))
+        .unwrap_or_default()
+}
+
+/// Records an accepted answer and the star it earned.
+pub fn record_star(day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    if !matches!(part, 1 | 2) {
+        return Err(Error::InvalidArguments(format!("unknown part {part}")));
+    }
+
+    let path = get_path_for_answers(day);
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e).with_context(|| format!("failed to read \"{}\"", path.display())),
+    };
+    let stars = parse(&content).stars.max(part);
+
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)
+            .with_context(|| format!("failed to create \"{}\"", folder.display()))?;
+    }
+
+    fs::write(&path, update(&content, part, answer, stars))
+        .with_context(|| format!("failed to write \"{}\"", path.display()))
+}
+
+/// Sets the answer of `part` and the number of stars in the content of an answers file.
+/// Only the lines read by [`parse`] are replaced (or appended if they are missing), all other lines are kept.
+fn update(content: &str, part: u8, answer: &str, stars: u8) -> String {
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    let mut set = |key: &str, value: &str| {
+        let line = format!("{key}: {value}");
+        // `parse` reads the last line of a key, so that is the one to replace.
+        let existing = lines.iter().rposition(|line| {
+            line.split_once(':')
+                .is_some_and(|(other, _)| other.trim() == key)
+        });
+        match existing {
+            Some(index) => lines[index] = line,
+            None => lines.push(line),
+        }
+    };
+
+    set(&format!("Part {part}"), answer);
+    set("Stars", &stars.to_string());
+
+    lines.join("\n") + "\n"
+}
+
+fn parse(content: &str) -> Answers {
+    let mut answers = Answers::default();
+
+    for line in content.lines() {
+        let Some((part, answer)) = line.split_once(':') else {
+            continue;
+        };
+
+        let answer = answer.trim();
+        if answer.is_empty() {
+            continue;
+        }
+
+        match part.trim() {
+            "Part 1" => answers.part_1 = Some(answer.into()),
+            "Part 2" => answers.part_2 = Some(answer.into()),
+            "Stars" => answers.stars = answer.parse::<u8>().unwrap_or_default().min(2),
+            _ => {}
+        }
+    }
+
+    answers
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, update, Answers};
+
+    #[test]
+    fn parses_both_parts() {
+        let answers = parse("Part 1: 142\nPart 2: EUBUERUL\n");
+        assert_eq!(
+            answers,
+            Answers {
+                part_1: Some("142".into()),
+                part_2: Some("EUBUERUL".into()),
+                stars: 0,
+            }
+        );
+        assert_eq!(answers.get(2), Some("EUBUERUL"));
+    }
+
+    #[test]
+    fn round_trips_stars() {
+        let answers = parse("Part 1: 142\nStars: 1\n");
+        assert_eq!(answers.stars, 1);
+        assert_eq!(answers.count(), 1);
+        assert_eq!(parse(&answers.to_string()), answers);
+    }
+
+    #[test]
+    fn records_answers_and_keeps_other_lines() {
+        let content = "# day 1\nPart 1: 142\n\n# TODO: part 2\nStars: 1\n";
+
+        let updated = update(content, 2, "281", 2);
+        assert_eq!(
+            updated,
+            "# day 1\nPart 1: 142\n\n# TODO: part 2\nStars: 2\nPart 2: 281\n"
+        );
+        assert_eq!(parse(&updated).get(2), Some("281"));
+
+        let updated = update(&updated, 1, "143", 2);
+        assert!(updated.starts_with("# day 1\nPart 1: 143\n\n# TODO: part 2\n"));
+        assert_eq!(update("", 1, "7", 1), "Part 1: 7\nStars: 1\n");
+    }
+
+    #[test]
+    fn ignores_unknown_and_empty_lines() {
+        let answers = parse("# day 1\n\nPart 1:\nPart 2:  281  \nPart 3: 1");
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("281"));
+        assert_eq!(answers.get(3), None);
+    }
+}
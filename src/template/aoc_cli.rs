@@ -1,41 +1,51 @@
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
 /// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
+use std::{env, fs};
 
+use crate::template::{config, error::Error};
 use crate::Day;
 
-#[derive(Debug)]
-pub enum AocCommandError {
-    CommandNotFound,
-    CommandNotCallable,
-    BadExitStatus(Output),
-    IoError,
-}
-
-impl Display for AocCommandError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-            AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
-            }
-            AocCommandError::IoError => write!(f, "could not write output files to file system."),
-        }
-    }
-}
-
-pub fn check() -> Result<(), AocCommandError> {
+pub fn check() -> Result<(), Error> {
     Command::new("aoc")
         .arg("-V")
         .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
+        .map_err(|_| Error::AocCliNotFound)?;
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
+/// Checks whether a session cookie is configured in one of the places aoc-cli looks for it.
+#[must_use]
+pub fn has_session() -> bool {
+    session().is_some()
+}
+
+/// Returns the session cookie from the places aoc-cli looks for it:
+/// the `ADVENT_OF_CODE_SESSION` environment variable, `~/.adventofcode.session` or `~/.config/adventofcode.session`.
+#[must_use]
+pub fn session() -> Option<String> {
+    if let Some(session) = env::var("ADVENT_OF_CODE_SESSION")
+        .ok()
+        .filter(|session| !session.is_empty())
+    {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from)?;
+    let config_dir =
+        env::var_os("XDG_CONFIG_HOME").map_or_else(|| home.join(".config"), PathBuf::from);
+
+    [
+        home.join(".adventofcode.session"),
+        config_dir.join("adventofcode.session"),
+    ]
+    .iter()
+    .filter_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .find(|session| !session.is_empty())
+}
+
+pub fn read(day: Day) -> Result<Output, Error> {
     let puzzle_path = get_puzzle_path(day);
 
     let args = build_args(
@@ -51,7 +61,7 @@ pub fn read(day: Day) -> Result<Output, AocCommandError> {
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
+pub fn download(day: Day) -> Result<Output, Error> {
     let input_path = get_input_path(day);
     let puzzle_path = get_puzzle_path(day);
 
@@ -74,27 +84,41 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+/// Submits an answer. Returns whether it was accepted.
+pub fn submit(day: Day, part: u8, result: &str) -> Result<bool, Error> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| Error::AocCliNotFound)?;
+
+    // the output is captured to find out whether the answer was right, print it like the other commands do.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{stdout}");
+
+    if output.status.success() {
+        Ok(stdout.contains("That's the right answer"))
+    } else {
+        Err(Error::AocCliFailed(output.status))
+    }
 }
 
 fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+    config::get().paths.input(day).display().to_string()
 }
 
 fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+    config::get().paths.puzzle(day).display().to_string()
 }
 
 fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
+    config::get().year
 }
 
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
@@ -110,18 +134,18 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+fn call_aoc_cli(args: &[String]) -> Result<Output, Error> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
+        .map_err(|_| Error::AocCliNotFound)?;
 
     if output.status.success() {
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(Error::AocCliFailed(output.status))
     }
 }
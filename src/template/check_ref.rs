@@ -0,0 +1,157 @@
+/// Module that cross-checks solution parts against their naive implementations, see `cargo check-ref`.
+use std::fmt::Display;
+
+use crate::template::outcome::Outcome;
+use crate::template::runner::{catch_panic, CaughtPanic};
+
+/// Longer failing inputs are not printed, their description tells how to get them.
+const MAX_PRINTED_LINES: usize = 40;
+
+/// An input that both implementations of a part are run on.
+pub struct CheckInput {
+    /// Tells where the input comes from, e.g. `the example`.
+    pub description: String,
+    pub input: String,
+    /// Truncated inputs may be invalid, they are skipped if a part panics or returns `None`.
+    pub may_be_invalid: bool,
+}
+
+/// Runs `solve` and `naive` on every input and stops at the first disagreement, which is printed with its input.
+pub fn check<T: Display + PartialEq>(
+    part: u8,
+    solve: fn(&str) -> Option<T>,
+    naive: fn(&str) -> Option<T>,
+    inputs: &[CheckInput],
+) -> Outcome {
+    let part_str = format!("Part {part}");
+    let (mut checked, mut skipped) = (0, 0);
+
+    for input in inputs {
+        let result = catch_panic(|| solve(&input.input));
+        let expected = catch_panic(|| naive(&input.input));
+
+        match (result, expected) {
+            (Ok(result), Ok(expected))
+                if input.may_be_invalid && (result.is_none() || expected.is_none()) =>
+            {
+                skipped += 1;
+            }
+            (Ok(result), Ok(expected)) if result == expected => checked += 1,
+            (Ok(result), Ok(expected)) => {
+                println!(
+                    "{part_str}: ✖ disagrees with the naive implementation on {}:",
+                    input.description
+                );
+                println!("  solution: {}", format_answer(result.as_ref()));
+                println!("  naive:    {}", format_answer(expected.as_ref()));
+                print_input(&input.input);
+                return Outcome::Regression;
+            }
+            (Err(_), _) | (_, Err(_)) if input.may_be_invalid => skipped += 1,
+            (Err(panic), _) => return report_panic(&part_str, "", input, &panic),
+            (_, Err(panic)) => return report_panic(&part_str, "naive ", input, &panic),
+        }
+    }
+
+    if checked == 0 {
+        println!("{part_str}: ✖ no inputs to check, add an example or register a generator.");
+        return Outcome::Error;
+    }
+
+    let skipped = match skipped {
+        0 => String::new(),
+        skipped => format!(", skipped {skipped} invalid truncated inputs"),
+    };
+    let inputs = if checked == 1 { "input" } else { "inputs" };
+    println!("{part_str}: ✔ agrees with the naive implementation on {checked} {inputs}{skipped}.");
+    Outcome::Success
+}
+
+fn report_panic(
+    part: &str,
+    implementation: &str,
+    input: &CheckInput,
+    panic: &CaughtPanic,
+) -> Outcome {
+    println!(
+        "{part}: ✖ the {implementation}implementation {panic}, on {}:",
+        input.description
+    );
+    print_input(&input.input);
+    Outcome::Panicked
+}
+
+fn format_answer<T: Display>(answer: Option<&T>) -> String {
+    answer.map_or_else(|| "None".into(), ToString::to_string)
+}
+
+fn print_input(input: &str) {
+    let lines = input.lines().count();
+    if lines > MAX_PRINTED_LINES {
+        println!("  (the input has {lines} lines, too many to print)");
+        return;
+    }
+
+    println!();
+    for line in input.lines() {
+        println!("  {line}");
+    }
+    println!();
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, CheckInput};
+    use crate::template::outcome::Outcome;
+
+    fn inputs(may_be_invalid: bool) -> Vec<CheckInput> {
+        ["1 2 3", "4 x", "10"]
+            .into_iter()
+            .map(|input| CheckInput {
+                description: format!("\"{input}\""),
+                input: input.into(),
+                may_be_invalid,
+            })
+            .collect()
+    }
+
+    fn sum(input: &str) -> Option<u32> {
+        input.split(' ').map(|n| n.parse::<u32>().ok()).sum()
+    }
+
+    fn sum_by_loop(input: &str) -> Option<u32> {
+        let mut total = 0;
+        for n in input.split(' ') {
+            total += n.parse::<u32>().ok()?;
+        }
+        Some(total)
+    }
+
+    fn sum_or_panic(input: &str) -> Option<u32> {
+        Some(input.split(' ').map(|n| n.parse::<u32>().unwrap()).sum())
+    }
+
+    fn first(input: &str) -> Option<u32> {
+        input.split(' ').next()?.parse().ok()
+    }
+
+    #[test]
+    fn agrees_on_equal_answers() {
+        assert_eq!(check(1, sum, sum_by_loop, &inputs(false)), Outcome::Success);
+    }
+
+    #[test]
+    fn reports_disagreements() {
+        assert_eq!(check(1, sum, first, &inputs(false)), Outcome::Regression);
+    }
+
+    #[test]
+    fn skips_invalid_truncated_inputs() {
+        assert_eq!(
+            check(1, sum_or_panic, sum, &inputs(false)),
+            Outcome::Panicked
+        );
+        assert_eq!(check(1, sum_or_panic, sum, &inputs(true)), Outcome::Success);
+        assert_eq!(check(1, sum, first, &inputs(true)), Outcome::Regression);
+    }
+}
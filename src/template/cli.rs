@@ -0,0 +1,551 @@
+//! Module that describes the commands and flags of the CLI, used for help, suggestions and shell completions.
+//! Parsing happens in `main.rs`, keep both in sync when adding a command or flag.
+
+/// A command of the CLI, e.g. `solve`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Command {
+    pub name: &'static str,
+    pub about: &'static str,
+    /// The free argument of the command, e.g. the day for `solve`.
+    pub positional: Option<Value>,
+    pub flags: &'static [Flag],
+    /// Example invocations, shown in the help of the command.
+    pub examples: &'static [&'static str],
+}
+
+/// A flag of a command, e.g. `--submit <part>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flag {
+    /// The name without leading dashes.
+    pub name: &'static str,
+    pub about: &'static str,
+    /// The value that follows the flag, [`None`] for switches.
+    pub value: Option<Value>,
+}
+
+/// The kind of a value, which decides how it is completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Value {
+    /// Any day from 1 to 25.
+    Day,
+    /// A day that has a solution in the bin folder.
+    ScaffoldedDay,
+    /// A [`DaySet`](crate::template::day_set::DaySet), e.g. `3..7,odd`.
+    Days,
+    /// A [`DaySet`](crate::template::day_set::DaySet) that may be left out to select all days.
+    OptionalDays,
+    /// `1` or `2`.
+    Part,
+    /// One of [`SHELLS`].
+    Shell,
+    /// A file path, with a name for the placeholder.
+    Path(&'static str),
+    /// Free text, with a name for the placeholder.
+    Text(&'static str),
+    /// Free text that may be left out.
+    OptionalText(&'static str),
+}
+
+impl Value {
+    /// The placeholder shown for the value, e.g. `<day>` or `[filter]` if it is optional.
+    #[must_use]
+    pub fn placeholder(self) -> String {
+        match self {
+            Self::Day | Self::ScaffoldedDay => "<day>".into(),
+            Self::Days => "<days>".into(),
+            Self::OptionalDays => "[days]".into(),
+            Self::Part => "<part>".into(),
+            Self::Shell => "<shell>".into(),
+            Self::Path(name) | Self::Text(name) => format!("<{name}>"),
+            Self::OptionalText(name) => format!("[{name}]"),
+        }
+    }
+}
+
+pub const SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+const fn switch(name: &'static str, about: &'static str) -> Flag {
+    Flag {
+        name,
+        about,
+        value: None,
+    }
+}
+
+const fn option(name: &'static str, value: Value, about: &'static str) -> Flag {
+    Flag {
+        name,
+        about,
+        value: Some(value),
+    }
+}
+
+const RELEASE: Flag = switch("release", "Build the solution in release mode");
+const TIME: Flag = switch("time", "Benchmark the solution");
+const TIMEOUT: Flag = option(
+    "timeout",
+    Value::Text("duration"),
+    "Stop a solution after the given time, e.g. 10s",
+);
+
+/// Flags that every command accepts, they override a config setting.
+pub const GLOBAL_FLAGS: &[Flag] = &[
+    option(
+        "config",
+        Value::Path("file"),
+        "Read the config from another file",
+    ),
+    option(
+        "year",
+        Value::Text("year"),
+        "Use another year than the configured one",
+    ),
+    option(
+        "inputs-dir",
+        Value::Path("dir"),
+        "Read inputs from another folder",
+    ),
+    option(
+        "examples-dir",
+        Value::Path("dir"),
+        "Read examples from another folder",
+    ),
+    option(
+        "puzzles-dir",
+        Value::Path("dir"),
+        "Store puzzle descriptions in another folder",
+    ),
+    option(
+        "answers-dir",
+        Value::Path("dir"),
+        "Read known answers from another folder",
+    ),
+    option(
+        "bin-dir",
+        Value::Path("dir"),
+        "Look for solutions in another folder",
+    ),
+];
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "scaffold",
+        about: "Create the solution, input and example files for a day",
+        positional: Some(Value::Day),
+        flags: &[],
+        examples: &["cargo scaffold 1"],
+    },
+    Command {
+        name: "download",
+        about: "Download the inputs and puzzle descriptions of some days",
+        positional: Some(Value::Days),
+        flags: &[],
+        examples: &["cargo download 1", "cargo download 1..5,last"],
+    },
+    Command {
+        name: "read",
+        about: "Show the puzzle description of a day in the terminal",
+        positional: Some(Value::Day),
+        flags: &[],
+        examples: &["cargo read 1"],
+    },
+    Command {
+        name: "solve",
+        about: "Run the solution of a day",
+        positional: Some(Value::ScaffoldedDay),
+        flags: &[
+            RELEASE,
+            TIME,
+            option("submit", Value::Part, "Submit the answer of a part"),
+            TIMEOUT,
+            option(
+                "trace",
+                Value::OptionalText("filter"),
+                "Log tracing events, filtered like RUST_LOG",
+            ),
+            option(
+                "trace-out",
+                Value::Path("file"),
+                "Write spans as a Chrome trace",
+            ),
+            option(
+                "trace-folded",
+                Value::Path("file"),
+                "Write spans as folded stacks",
+            ),
+            option(
+                "loop-part",
+                Value::Part,
+                "Run a part in a loop for profiling, with debug symbols",
+            ),
+            option(
+                "duration",
+                Value::Text("duration"),
+                "How long to loop the part, defaults to 10s",
+            ),
+            option(
+                "visualize",
+                Value::Path("dir"),
+                "Write the visualizations of the day to a folder",
+            ),
+            switch(
+                "variants",
+                "Benchmark the variants of the parts against each other",
+            ),
+        ],
+        examples: &[
+            "cargo solve 1",
+            "cargo solve 1 --release --time",
+            "cargo solve 1 --submit 2",
+            "cargo solve 5 --trace advent_of_code=debug",
+            "cargo solve 6 --release --variants",
+            "cargo solve 5 --loop-part 2 --duration 30s",
+        ],
+    },
+    Command {
+        name: "all",
+        about: "Run the solutions of all days, or of some days",
+        positional: Some(Value::OptionalDays),
+        flags: &[
+            RELEASE,
+            TIME,
+            TIMEOUT,
+            switch("fail-fast", "Stop at the first failing day"),
+            switch(
+                "allow-partial",
+                "Update the readme benchmarks even if not all days ran and succeeded",
+            ),
+        ],
+        examples: &[
+            "cargo all",
+            "cargo all 1..5,odd",
+            "cargo all unsolved --release --time",
+            "cargo all --timeout 10s --fail-fast",
+        ],
+    },
+    Command {
+        name: "test-day",
+        about: "Run the tests of some days",
+        positional: Some(Value::Days),
+        flags: &[],
+        examples: &["cargo test-day 3", "cargo test-day unsolved"],
+    },
+    Command {
+        name: "gen",
+        about: "Generate a random input for a day that has a generator",
+        positional: Some(Value::ScaffoldedDay),
+        flags: &[
+            option(
+                "size",
+                Value::Text("n"),
+                "The size of the input, e.g. the number of lines",
+            ),
+            option(
+                "seed",
+                Value::Text("seed"),
+                "Seed of the random numbers, defaults to 0",
+            ),
+            option(
+                "out",
+                Value::Path("file"),
+                "Write to this file instead of data/generated/DD.txt",
+            ),
+        ],
+        examples: &[
+            "cargo gen 7 --size 100000 --seed 1",
+            "cargo solve 7 --inputs-dir data/generated --release --time",
+        ],
+    },
+    Command {
+        name: "check-ref",
+        about: "Check the parts of a day against their naive implementations",
+        positional: Some(Value::ScaffoldedDay),
+        flags: &[RELEASE],
+        examples: &["cargo check-ref 6", "cargo check-ref 5 --release"],
+    },
+    Command {
+        name: "status",
+        about: "Show what exists for each day and how far it got",
+        positional: Some(Value::OptionalDays),
+        flags: &[
+            switch("json", "Print the status as JSON"),
+            switch("no-tests", "Do not run the tests of each day"),
+        ],
+        examples: &[
+            "cargo status",
+            "cargo status 1..5",
+            "cargo status --no-tests --json",
+        ],
+    },
+    Command {
+        name: "calendar",
+        about: "Draw the advent calendar with the stars of each day",
+        positional: None,
+        flags: &[switch("plain", "Leave out colors and links")],
+        examples: &["cargo calendar", "cargo calendar --plain"],
+    },
+    Command {
+        name: "progress",
+        about: "Update the progress table in the readme",
+        positional: None,
+        flags: &[],
+        examples: &["cargo progress"],
+    },
+    Command {
+        name: "leaderboard",
+        about: "Show a private leaderboard",
+        positional: Some(Value::Text("id")),
+        flags: &[
+            switch("offline", "Show the cached leaderboard without fetching it"),
+            option(
+                "file",
+                Value::Path("file"),
+                "Read the leaderboard from a JSON file",
+            ),
+        ],
+        examples: &[
+            "cargo leaderboard 123456",
+            "cargo leaderboard 123456 --offline",
+        ],
+    },
+    Command {
+        name: "completions",
+        about: "Print a shell completion script",
+        positional: Some(Value::Shell),
+        flags: &[],
+        examples: &[
+            "source <(cargo completions bash)",
+            "cargo completions fish | source",
+        ],
+    },
+];
+
+/// Looks up a command by name.
+#[must_use]
+pub fn find(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|command| command.name == name)
+}
+
+/// Renders the help of a command, or the overview of all commands for [`None`].
+#[must_use]
+pub fn help(command: Option<&Command>) -> String {
+    let mut lines = vec![];
+
+    match command {
+        None => {
+            lines.push("Usage: cargo <command> [flags]".into());
+            lines.push(String::new());
+            lines.push("Commands:".into());
+            let width = COMMANDS.iter().map(|command| command.name.len()).max();
+            for command in COMMANDS {
+                lines.push(format!(
+                    "  {:width$}  {}",
+                    command.name,
+                    command.about,
+                    width = width.unwrap_or_default()
+                ));
+            }
+        }
+        Some(command) => {
+            lines.push(command.about.into());
+            lines.push(String::new());
+            lines.push(format!(
+                "Usage: cargo {}{} [flags]",
+                command.name,
+                command
+                    .positional
+                    .map(|value| format!(" {}", value.placeholder()))
+                    .unwrap_or_default()
+            ));
+            if !command.flags.is_empty() {
+                lines.push(String::new());
+                lines.push("Flags:".into());
+                lines.extend(flag_lines(command.flags));
+            }
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("Global flags:".into());
+    lines.extend(flag_lines(GLOBAL_FLAGS));
+    lines.push(format!("  {:22}  Print help", "-h, --help"));
+
+    match command {
+        None => {
+            lines.push(String::new());
+            lines.push("Run `cargo <command> --help` for the flags of a command.".into());
+        }
+        Some(command) if !command.examples.is_empty() => {
+            lines.push(String::new());
+            lines.push("Examples:".into());
+            lines.extend(
+                command
+                    .examples
+                    .iter()
+                    .map(|example| format!("  {example}")),
+            );
+        }
+        Some(_) => {}
+    }
+
+    lines.iter().map(|line| format!("{line}\n")).collect()
+}
+
+fn flag_lines(flags: &[Flag]) -> impl Iterator<Item = String> + '_ {
+    flags.iter().map(|flag| {
+        let usage = match flag.value {
+            Some(value) => format!("--{} {}", flag.name, value.placeholder()),
+            None => format!("--{}", flag.name),
+        };
+        format!("  {usage:22}  {}", flag.about)
+    })
+}
+
+/// Checks the arguments that are left after parsing a command.
+/// Errors for the first one, suggesting a similar flag if it looks like a typo.
+pub fn check_unused(command: &Command, unused: &[String]) -> Result<(), String> {
+    let Some(arg) = unused.first() else {
+        return Ok(());
+    };
+
+    if !arg.starts_with('-') {
+        return Err(format!(
+            "unexpected argument \"{arg}\" for `{}`.",
+            command.name
+        ));
+    }
+
+    let flags = command
+        .flags
+        .iter()
+        .chain(GLOBAL_FLAGS)
+        .map(|flag| format!("--{}", flag.name));
+
+    Err(match suggest(arg, flags) {
+        Some(flag) => format!(
+            "unknown flag \"{arg}\" for `{}`, did you mean \"{flag}\"?",
+            command.name
+        ),
+        None => format!(
+            "unknown flag \"{arg}\" for `{}`, see `cargo {} --help`.",
+            command.name, command.name
+        ),
+    })
+}
+
+/// Returns the candidate that is closest to `input`, if it is close enough to be a typo.
+pub fn suggest<S: AsRef<str>>(input: &str, candidates: impl IntoIterator<Item = S>) -> Option<S> {
+    let max_distance = (input.len() / 3).max(1);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate.as_ref()), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// The edit distance between two strings, counting swapped neighbours as one edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] is the distance between the first `i` chars of `a` and the first `j` chars of `b`.
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j - 1] + cost)
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_unused, edit_distance, find, help, suggest};
+
+    #[test]
+    fn computes_edit_distance() {
+        assert_eq!(edit_distance("--time", "--time"), 0);
+        assert_eq!(edit_distance("--tme", "--time"), 1);
+        assert_eq!(edit_distance("--relaese", "--release"), 1);
+        assert_eq!(edit_distance("--relase", "--release"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn suggests_close_candidates() {
+        let commands = ["solve", "scaffold", "status"];
+        assert_eq!(suggest("slove", commands), Some("solve"));
+        assert_eq!(suggest("statsu", commands), Some("status"));
+        assert_eq!(suggest("benchmark", commands), None);
+    }
+
+    #[test]
+    fn errors_for_unknown_flags() {
+        let solve = find("solve").unwrap();
+        assert_eq!(check_unused(solve, &[]), Ok(()));
+        assert_eq!(
+            check_unused(solve, &["--tme".into()]),
+            Err("unknown flag \"--tme\" for `solve`, did you mean \"--time\"?".into())
+        );
+        assert_eq!(
+            check_unused(solve, &["--yaer".into()]),
+            Err("unknown flag \"--yaer\" for `solve`, did you mean \"--year\"?".into())
+        );
+        assert_eq!(
+            check_unused(solve, &["--verbose".into()]),
+            Err("unknown flag \"--verbose\" for `solve`, see `cargo solve --help`.".into())
+        );
+        assert_eq!(
+            check_unused(solve, &["02".into()]),
+            Err("unexpected argument \"02\" for `solve`.".into())
+        );
+    }
+
+    #[test]
+    fn renders_command_help() {
+        let help = help(find("solve"));
+        let lines: Vec<&str> = help.lines().collect();
+
+        assert_eq!(lines[0], "Run the solution of a day");
+        assert_eq!(lines[2], "Usage: cargo solve <day> [flags]");
+        assert!(lines.contains(&"  --submit <part>         Submit the answer of a part"));
+        assert!(
+            lines.contains(&"  --trace [filter]        Log tracing events, filtered like RUST_LOG")
+        );
+        assert!(lines.contains(&"  -h, --help              Print help"));
+        assert_eq!(
+            lines.last(),
+            Some(&"  cargo solve 5 --loop-part 2 --duration 30s")
+        );
+    }
+
+    #[test]
+    fn renders_overview() {
+        let help = help(None);
+
+        assert!(
+            help.starts_with("Usage: cargo <command> [flags]\n\nCommands:\n  scaffold     Create")
+        );
+        assert!(help.contains("\n  completions  Print a shell completion script\n"));
+        assert!(help.ends_with("Run `cargo <command> --help` for the flags of a command.\n"));
+    }
+}
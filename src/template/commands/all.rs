@@ -1,85 +1,161 @@
-use std::io;
+use std::path::Path;
+use std::time::Duration;
 
 use crate::template::{
+    config,
+    error::{self, Context, Error},
+    outcome::Outcome,
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
-
-pub fn handle(is_release: bool, is_timed: bool) {
+use crate::Day;
+
+use super::{build_solution, Profile};
+
+/// Runs the given days and returns the worst [`Outcome`], days that are not scaffolded are skipped.
+/// With `fail_fast`, stops after the first day that did not succeed.
+///
+/// Timed release runs update the benchmarks in the readme, but only if all days were run and each of them succeeded,
+/// unless `allow_partial` is set.
+pub fn handle(
+    days: &[Day],
+    is_release: bool,
+    is_timed: bool,
+    timeout: Option<Duration>,
+    fail_fast: bool,
+    allow_partial: bool,
+) -> Result<Outcome, Error> {
     let mut timings: Vec<Timings> = vec![];
+    let mut failures: Vec<Failure> = vec![];
+    let mut outcome = Outcome::Success;
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    for (i, &day) in days.iter().enumerate() {
+        if i > 0 {
             println!();
         }
 
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
-
-        if output.is_empty() {
+        // skip days that have not been scaffolded yet.
+        if !Path::new(&get_path_for_bin(day)).exists() {
             println!("Not solved.");
+            continue;
+        }
+
+        let is_built =
+            build_solution(day, Profile::new(is_release)).context("failed to call cargo")?;
+
+        let day_outcome = if is_built {
+            let run = child_commands::run_solution(day, is_timed, is_release, timeout)
+                .with_context(|| format!("failed to run day {day}"))?;
+
+            failures.extend(
+                child_commands::parse_failures(&run.output)
+                    .into_iter()
+                    .map(|reason| Failure { day, reason }),
+            );
+
+            if !run.output.is_empty() {
+                let val = child_commands::parse_exec_time(&run.output, day);
+                timings.push(val);
+            }
+
+            run.outcome
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            Outcome::BuildFailed
+        };
+
+        if !day_outcome.is_success() && !failures.iter().any(|f| f.day == day) {
+            failures.push(Failure {
+                day,
+                reason: day_outcome.to_string(),
+            });
+        }
+
+        outcome = outcome.max(day_outcome);
+
+        if fail_fast && !day_outcome.is_success() {
+            break;
         }
-    });
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
+        let partial = if days.len() < 25 {
+            Some("not all days were run")
+        } else if !outcome.is_success() {
+            Some("not every day produced the known answers")
+        } else {
+            None
+        };
+
+        match partial {
+            Some(reason) if is_release && !allow_partial => println!(
+                "Skipped updating the readme, as {reason}. Append `--allow-partial` to update it anyway."
+            ),
+            _ if is_release => match readme_benchmarks::update(timings, total_millis)
+                .context("failed to update readme with benchmarks")
+            {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
-            }
+                Err(e) => error::report(&e),
+            },
+            _ => {}
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failures:{ANSI_RESET}");
+        for failure in &failures {
+            println!("Day {}: {}", failure.day, failure.reason);
+        }
+    }
+
+    Ok(outcome)
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+/// A day that did not succeed while running all days, e.g. because a part panicked or an answer regressed.
+struct Failure {
+    day: Day,
+    reason: String,
 }
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Path::new(".")
+        .join(config::get().paths.bin(day))
+        .display()
+        .to_string()
 }
 
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use crate::template::{commands::wait_with_timeout, error::Error, outcome::Outcome};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
-        path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// The stdout lines and outcome of a solution bin.
+    pub struct SolutionRun {
+        pub output: Vec<String>,
+        pub outcome: Outcome,
+    }
 
+    /// Run the solution bin for a given day, killing it if it does not exit within `timeout`.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        timeout: Option<Duration>,
+    ) -> Result<SolutionRun, Error> {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
@@ -102,27 +178,53 @@ mod child_commands {
             .stderr(Stdio::piped())
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or(Error::BrokenPipe)?);
 
-        let thread = thread::spawn(move || {
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
                 eprintln!("{}", line.unwrap());
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        let stdout_thread = thread::spawn(move || {
+            stdout
+                .lines()
+                .map(|line| {
+                    let line = line.unwrap();
+                    println!("{line}");
+                    line
+                })
+                .collect::<Vec<String>>()
+        });
+
+        let status = wait_with_timeout(&mut cmd, timeout)?;
+
+        let output = stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let outcome = match status {
+            Some(status) => status.code().map_or(Outcome::Error, Outcome::from_code),
+            None => {
+                println!("✖ timed out");
+                Outcome::Timeout
+            }
+        };
+
+        Ok(SolutionRun { output, outcome })
+    }
 
-        Ok(output)
+    /// Collects the parts that panicked or regressed from the output of a solution bin.
+    pub fn parse_failures(output: &[String]) -> Vec<String> {
+        output
+            .iter()
+            .filter_map(|l| {
+                // intermediate output is overwritten with a carriage return, only the last segment is visible.
+                let (part, reason) = l.rsplit('\r').next()?.split_once(": ✖ ")?;
+                let reason = reason.trim();
+                (!reason.is_empty()).then(|| format!("{part}: {reason}"))
+            })
+            .collect()
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
@@ -171,7 +273,7 @@ mod child_commands {
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
@@ -202,7 +304,7 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_failures};
 
         use crate::day;
 
@@ -250,5 +352,24 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_failed_parts() {
+            let res = parse_failures(&[
+                "Part 1: ✖ panicked at src/bin/01.rs:9:48: should be a number".into(),
+                "Part 2: 42 > benching\rPart 2: ✖ panicked: Box<dyn Any>".into(),
+                "Part 3: ✖             ".into(),
+                "Part 4: 42 (1.0ms)".into(),
+                "Part 4: ✖ expected 43".into(),
+            ]);
+            assert_eq!(
+                res,
+                vec![
+                    "Part 1: panicked at src/bin/01.rs:9:48: should be a number",
+                    "Part 2: panicked: Box<dyn Any>",
+                    "Part 4: expected 43",
+                ]
+            );
+        }
     }
 }
@@ -0,0 +1,136 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::{env, io};
+
+use crate::template::date::Date;
+use crate::template::{answers, config, ANSI_BOLD, ANSI_DIM, ANSI_RESET, ANSI_YELLOW};
+use crate::{all_days, Day};
+
+/// A day as shown in the calendar.
+struct CalendarDay {
+    day: Day,
+    /// Solved parts, see [`answers::Answers::progress`].
+    stars: u8,
+    /// The downloaded puzzle description, linked from the day number.
+    puzzle: Option<PathBuf>,
+}
+
+/// Draws the 25 days of advent as a 5x5 grid, colored by progress.
+/// Colors and links are only used if stdout is a terminal and `NO_COLOR` is not set, or can be turned off with `plain`.
+pub fn handle(plain: bool) {
+    let today = Date::today();
+    let year = config::get()
+        .year
+        .map_or_else(|| today.latest_event(), i64::from);
+    let current_day = today.advent_day().filter(|_| today.year == year);
+
+    let days: Vec<CalendarDay> = all_days()
+        .map(|day| {
+            let puzzle = config::get().paths.puzzle(day);
+            CalendarDay {
+                day,
+                stars: answers::read(day).progress(),
+                puzzle: puzzle
+                    .exists()
+                    .then(|| puzzle.canonicalize().unwrap_or(puzzle)),
+            }
+        })
+        .collect();
+
+    let color = !plain && io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    print!("{}", render(year, &days, current_day, color));
+}
+
+fn render(year: i64, days: &[CalendarDay], current_day: Option<u8>, color: bool) -> String {
+    let style = |code: &str, text: &str| {
+        if color {
+            format!("{code}{text}{ANSI_RESET}")
+        } else {
+            text.to_string()
+        }
+    };
+
+    let mut out = format!("{}\n", style(ANSI_BOLD, &format!("Advent of Code {year}")));
+    let border = |left: &str, middle: &str, right: &str| {
+        format!("{left}{}{right}\n", ["────────"; 5].join(middle))
+    };
+
+    out.push_str(&border("┌", "┬", "┐"));
+
+    for (row, week) in days.chunks(5).enumerate() {
+        if row > 0 {
+            out.push_str(&border("├", "┼", "┤"));
+        }
+
+        out.push('│');
+        for day in week {
+            let is_current = current_day.is_some_and(|current| day.day == current);
+            let number = if is_current {
+                format!("[{}]", day.day)
+            } else {
+                format!(" {} ", day.day)
+            };
+
+            let number = match &day.puzzle {
+                // OSC 8 hyperlink, supported by most terminals and ignored by the rest.
+                Some(path) if color => format!(
+                    "\x1b]8;;file://{}\x1b\\{number}\x1b]8;;\x1b\\",
+                    path.display()
+                ),
+                _ => number,
+            };
+            let number = if is_current {
+                style(ANSI_BOLD, &number)
+            } else {
+                number
+            };
+
+            let stars = match day.stars {
+                0 => style(ANSI_DIM, "··"),
+                1 => format!("{}{}", style(ANSI_YELLOW, "★"), style(ANSI_DIM, "·")),
+                _ => style(ANSI_YELLOW, "★★"),
+            };
+
+            out.push_str(&format!("{number} {stars} │"));
+        }
+        out.push('\n');
+    }
+
+    out.push_str(&border("└", "┴", "┘"));
+
+    let stars: u32 = days.iter().map(|day| u32::from(day.stars)).sum();
+    out.push_str(&format!("{stars}/50 ★\n"));
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, CalendarDay};
+    use crate::all_days;
+
+    #[test]
+    fn renders_plain_grid() {
+        let days: Vec<CalendarDay> = all_days()
+            .map(|day| CalendarDay {
+                day,
+                stars: match day.into_inner() {
+                    1 => 2,
+                    2 => 1,
+                    _ => 0,
+                },
+                puzzle: None,
+            })
+            .collect();
+
+        let output = render(2023, &days, Some(3), false);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "Advent of Code 2023");
+        assert_eq!(lines[1], "┌────────┬────────┬────────┬────────┬────────┐");
+        assert_eq!(lines[2], "│ 01  ★★ │ 02  ★· │[03] ·· │ 04  ·· │ 05  ·· │");
+        assert_eq!(lines[10], "│ 21  ·· │ 22  ·· │ 23  ·· │ 24  ·· │ 25  ·· │");
+        assert_eq!(lines[12], "3/50 ★");
+        assert!(!output.contains('\x1b'));
+    }
+}
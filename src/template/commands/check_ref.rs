@@ -0,0 +1,29 @@
+use std::process::{Command, Stdio};
+
+use crate::template::error::{Context, Error};
+use crate::template::outcome::Outcome;
+use crate::Day;
+
+use super::{build_solution, Profile};
+
+/// Runs the solution bin of a day in check mode, comparing its parts with their naive implementations.
+pub fn handle(day: Day, release: bool) -> Result<Outcome, Error> {
+    let profile = Profile::new(release);
+    if !build_solution(day, profile).context("failed to call cargo")? {
+        return Ok(Outcome::BuildFailed);
+    }
+
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    args.extend(profile.cargo_args());
+    args.extend(["--", "--check-ref"]);
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .context("failed to call cargo")?;
+
+    Ok(status.code().map_or(Outcome::Error, Outcome::from_code))
+}
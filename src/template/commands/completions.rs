@@ -0,0 +1,6 @@
+use crate::template::completions::{self, Shell};
+use crate::template::config;
+
+pub fn handle(shell: Shell) {
+    print!("{}", completions::generate(shell, &config::get().paths.bin));
+}
@@ -1,15 +1,11 @@
 use crate::template::aoc_cli;
+use crate::template::error::{Context, Error};
 use crate::Day;
-use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+pub fn handle(days: &[Day]) -> Result<(), Error> {
+    aoc_cli::check()?;
+    for &day in days {
+        aoc_cli::download(day).with_context(|| format!("failed to call aoc-cli for day {day}"))?;
     }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    Ok(())
 }
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::template::config;
+use crate::template::error::{Context, Error};
+use crate::template::outcome::Outcome;
+use crate::Day;
+
+use super::{build_solution, Profile};
+
+/// Runs the generator of a day and writes the generated input to `out`, or to `data/generated/DD.txt`.
+pub fn handle(day: Day, size: usize, seed: u64, out: Option<PathBuf>) -> Result<Outcome, Error> {
+    if !build_solution(day, Profile::Release).context("failed to call cargo")? {
+        return Ok(Outcome::BuildFailed);
+    }
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &day.to_string(),
+            "--",
+        ])
+        .args([
+            "--generate",
+            "--size",
+            &size.to_string(),
+            "--seed",
+            &seed.to_string(),
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .context("failed to call cargo")?;
+
+    if !output.status.success() {
+        return Ok(output
+            .status
+            .code()
+            .map_or(Outcome::Error, Outcome::from_code));
+    }
+
+    let generated = config::get().paths.folder("generated");
+    let path = out.unwrap_or_else(|| generated.join(format!("{day}.txt")));
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create \"{}\"", parent.display()))?;
+    }
+    fs::write(&path, &output.stdout)
+        .with_context(|| format!("failed to write \"{}\"", path.display()))?;
+
+    println!(
+        "Generated an input of size {size} with seed {seed} and wrote it to \"{}\".",
+        path.display()
+    );
+    if path.parent() == Some(generated.as_path()) {
+        println!(
+            "Run `cargo solve {day} --inputs-dir {}` to solve it.",
+            generated.display()
+        );
+    }
+
+    Ok(Outcome::Success)
+}
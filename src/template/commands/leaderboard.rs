@@ -0,0 +1,157 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use crate::template::aoc_cli;
+use crate::template::config;
+use crate::template::date::Date;
+use crate::template::error::{Context, Error};
+use crate::template::leaderboard::{self, Leaderboard};
+
+/// Advent of Code asks to not fetch a private leaderboard more often than this.
+const MIN_FETCH_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Where a leaderboard comes from.
+pub enum Source<'a> {
+    /// Fetch it from adventofcode.com, unless the cached copy is recent.
+    Fetch,
+    /// Only show the cached copy.
+    Cache,
+    /// Import a saved JSON file.
+    File(&'a Path),
+}
+
+pub fn handle(id: &str, source: Source) -> Result<(), Error> {
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Error::InvalidArguments(format!(
+            "invalid leaderboard id \"{id}\", expecting the number in the leaderboard url."
+        )));
+    }
+
+    let path = get_path_for_leaderboard(id, false);
+
+    match source {
+        Source::Fetch if is_recent(&path) => {
+            println!("Showing the leaderboard fetched less than 15 minutes ago.");
+        }
+        Source::Fetch => {
+            let json = fetch(id)?;
+            Leaderboard::from_json(&json)
+                .context("unexpected response, is the session cookie still valid?")?;
+            save(id, &json)?;
+        }
+        Source::Cache => {}
+        Source::File(file) => save(
+            id,
+            &fs::read_to_string(file)
+                .with_context(|| format!("failed to read \"{}\"", file.display()))?,
+        )?,
+    }
+
+    let current = load(&path)?.ok_or_else(|| {
+        Error::Leaderboard(format!(
+            "no saved leaderboard for {id}. Run `cargo leaderboard {id}` without `--offline` to fetch it."
+        ))
+    })?;
+    let previous = load(&get_path_for_leaderboard(id, true))?;
+
+    print!("{}", leaderboard::render(id, &current, previous.as_ref()));
+    Ok(())
+}
+
+#[must_use]
+pub fn get_path_for_leaderboard(id: &str, previous: bool) -> PathBuf {
+    let file = if previous {
+        format!("{id}.previous.json")
+    } else {
+        format!("{id}.json")
+    };
+    config::get().paths.folder("leaderboards").join(file)
+}
+
+fn is_recent(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| modified.elapsed().is_ok_and(|age| age < MIN_FETCH_INTERVAL))
+}
+
+fn load(path: &Path) -> Result<Option<Leaderboard>, Error> {
+    match fs::read_to_string(path) {
+        Ok(json) => Leaderboard::from_json(&json)
+            .with_context(|| format!("failed to parse \"{}\"", path.display()))
+            .map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("failed to read \"{}\"", path.display())),
+    }
+}
+
+/// Stores a new copy of the leaderboard, the current copy becomes the previous one.
+fn save(id: &str, json: &str) -> Result<(), Error> {
+    Leaderboard::from_json(json)?;
+
+    let path = get_path_for_leaderboard(id, false);
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)
+            .with_context(|| format!("failed to create \"{}\"", folder.display()))?;
+    }
+
+    if path.exists() {
+        fs::rename(&path, get_path_for_leaderboard(id, true))
+            .context("failed to keep the previous leaderboard")?;
+    }
+
+    fs::write(&path, json).with_context(|| format!("failed to write \"{}\"", path.display()))
+}
+
+/// Fetches the leaderboard JSON with curl, using the session cookie of aoc-cli.
+/// aoc-cli only prints leaderboards as a table, so it can't be used for this.
+fn fetch(id: &str) -> Result<String, Error> {
+    let session = aoc_cli::session().ok_or_else(|| {
+        Error::Leaderboard(
+            "no session cookie found. See \"Configure aoc-cli integration\" in the readme.".into(),
+        )
+    })?;
+
+    let year = config::get()
+        .year
+        .map_or_else(|| Date::today().latest_event(), i64::from);
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    println!("Fetching leaderboard {id} for {year}...");
+
+    // the cookie is passed in a config read from stdin so that it does not show up in the process list.
+    let mut child = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--config", "-", &url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => Error::Leaderboard(
+                "command \"curl\" not found, install it to fetch leaderboards \
+                 or import a saved leaderboard with `--file <path>`."
+                    .into(),
+            ),
+            _ => Error::Leaderboard(format!("failed to run curl: {e}")),
+        })?;
+
+    child
+        .stdin
+        .take()
+        .ok_or(Error::BrokenPipe)?
+        .write_all(format!("cookie = \"session={session}\"\n").as_bytes())
+        .context("failed to pass the session cookie to curl")?;
+
+    let output = child.wait_with_output().context("failed to call curl")?;
+    if !output.status.success() {
+        return Err(Error::Leaderboard(format!(
+            "failed to fetch leaderboard {id}, curl exited with {}.",
+            output.status
+        )));
+    }
+
+    String::from_utf8(output.stdout)
+        .map_err(|_| Error::Leaderboard(format!("leaderboard {id} is not valid UTF-8.")))
+}
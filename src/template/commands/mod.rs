@@ -1,5 +1,93 @@
 pub mod all;
+pub mod calendar;
+pub mod check_ref;
+pub mod completions;
 pub mod download;
+pub mod generate;
+pub mod leaderboard;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
+pub mod test_day;
+
+use std::{
+    io,
+    process::{Child, Command, ExitStatus, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::Day;
+
+/// The cargo profile that solution bins are built with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Profile {
+    Dev,
+    Release,
+    /// Optimized like `release`, but with debug symbols for profilers, see `[profile.profiling]` in `Cargo.toml`.
+    Profiling,
+}
+
+impl Profile {
+    fn new(is_release: bool) -> Self {
+        if is_release {
+            Profile::Release
+        } else {
+            Profile::Dev
+        }
+    }
+
+    /// The arguments that select the profile in cargo commands.
+    fn cargo_args(self) -> &'static [&'static str] {
+        match self {
+            Profile::Dev => &[],
+            Profile::Release => &["--release"],
+            Profile::Profiling => &["--profile", "profiling"],
+        }
+    }
+}
+
+/// Builds the solution bin for a given day, returns `false` if compilation failed.
+/// Compiler output is forwarded to stderr.
+fn build_solution(day: Day, profile: Profile) -> io::Result<bool> {
+    let day_padded = day.to_string();
+    let mut args = vec!["build", "--quiet", "--bin", &day_padded];
+    args.extend(profile.cargo_args());
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .status()?;
+
+    Ok(status.success())
+}
+
+/// Waits for a child process to exit, killing it once `timeout` has passed.
+/// Returns [`None`] if the child was killed.
+fn wait_with_timeout(
+    child: &mut Child,
+    timeout: Option<Duration>,
+) -> io::Result<Option<ExitStatus>> {
+    let Some(timeout) = timeout else {
+        return child.wait().map(Some);
+    };
+
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Ok(None);
+        }
+
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,14 @@
+use crate::template::error::{Context, Error};
+use crate::template::readme_progress;
+
+pub fn handle() -> Result<(), Error> {
+    let progress = readme_progress::collect();
+    readme_progress::update(&progress).context("failed to update the progress table")?;
+
+    let stars: u32 = progress.iter().map(|day| u32::from(day.stars)).sum();
+    println!(
+        "Updated progress table in README.md: {} days, {stars} stars.",
+        progress.len()
+    );
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use std::{
     fs::{File, OpenOptions},
     io::Write,
-    process,
 };
 
+use crate::template::config;
+use crate::template::error::{Context, Error};
 use crate::Day;
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
@@ -39,56 +40,98 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(day: Day) -> Result<(), Error> {
+    let paths = &config::get().paths;
+    let input_path = paths.input(day).display().to_string();
+    let example_path = paths.example(day).display().to_string();
+    let module_path = paths.bin(day).display().to_string();
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let mut file = safe_create_file(&module_path)
+        .with_context(|| format!("failed to create module file \"{module_path}\""))?;
 
-    match file.write_all(
+    file.write_all(
         MODULE_TEMPLATE
             .replace("DAY_NUMBER", &day.into_inner().to_string())
             .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    )
+    .context("failed to write module contents")?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_file(&input_path)
+        .with_context(|| format!("failed to create input file \"{input_path}\""))?;
+    println!("Created empty input file \"{}\"", &input_path);
+
+    create_file(&example_path)
+        .with_context(|| format!("failed to create example file \"{example_path}\""))?;
+    println!("Created empty example file \"{}\"", &example_path);
+
+    println!("---");
+    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::handle;
+    use crate::day;
+    use serial_test::serial;
+    use std::{env, fs, path::PathBuf};
+
+    /// Creates an empty project layout in a temporary directory and makes it the working directory.
+    fn enter_temp_project(name: &str) -> (PathBuf, PathBuf) {
+        let previous = env::current_dir().unwrap();
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for folder in ["src/bin", "data/inputs", "data/examples"] {
+            fs::create_dir_all(dir.join(folder)).unwrap();
         }
+        env::set_current_dir(&dir).unwrap();
+        (previous, dir)
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    fn leave_temp_project((previous, dir): (PathBuf, PathBuf)) {
+        env::set_current_dir(previous).unwrap();
+        fs::remove_dir_all(dir).unwrap();
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    #[test]
+    #[serial]
+    fn creates_module_and_data_files() {
+        let project = enter_temp_project("create");
+        let result = handle(day!(3));
+        let module = fs::read_to_string("src/bin/03.rs").unwrap();
+        let has_data_files = ["data/inputs/03.txt", "data/examples/03.txt"]
+            .iter()
+            .all(|path| fs::metadata(path).is_ok());
+        leave_temp_project(project);
+
+        assert!(result.is_ok());
+        assert!(module.starts_with("advent_of_code::solution!(3);"));
+        assert!(has_data_files);
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    #[test]
+    #[serial]
+    fn errors_if_module_exists() {
+        let project = enter_temp_project("exists");
+        fs::write("src/bin/03.rs", "// my solution").unwrap();
+        let result = handle(day!(3));
+        let module = fs::read_to_string("src/bin/03.rs").unwrap();
+        leave_temp_project(project);
+
+        let error = result.unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "failed to create module file \"src/bin/03.rs\""
+        );
+        assert_eq!(module, "// my solution");
+    }
 }
@@ -1,16 +1,86 @@
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
+use crate::template::error::{Context, Error};
+use crate::template::outcome::Outcome;
+use crate::template::trace;
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+use super::{build_solution, wait_with_timeout, Profile};
+
+/// The tracing flags of `solve`, passed on to the solution binary.
+#[derive(Debug, Default)]
+pub struct TraceOptions {
+    /// Print events matching this filter.
+    pub filter: Option<String>,
+    /// Write a Chrome trace to this file.
+    pub out: Option<PathBuf>,
+    /// Write folded stacks to this file.
+    pub folded: Option<PathBuf>,
+}
+
+/// What the solution binary does, instead of running both parts once.
+#[derive(Debug, Default)]
+pub enum Mode {
+    #[default]
+    Parts,
+    /// Runs one part for a while, so that a profiler can attach to the process.
+    /// The solution binary loops for 10 seconds if no duration is given.
+    Loop {
+        part: u8,
+        duration: Option<Duration>,
+    },
+    /// Writes the visualizations of the day to a directory.
+    Visualize(PathBuf),
+    /// Benchmarks the variants of the parts against each other.
+    Variants,
+}
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    timeout: Option<Duration>,
+    trace: &TraceOptions,
+    mode: &Mode,
+) -> Result<Outcome, Error> {
+    if let Some(filter) = &trace.filter {
+        trace::parse_filter(filter)?;
+    }
+
+    // Looping is meant for profilers, which need optimized code with debug symbols.
+    let profile = match mode {
+        Mode::Loop { .. } => Profile::Profiling,
+        _ => Profile::new(release),
+    };
 
-    if release {
-        cmd_args.push("--release".to_string());
+    if !build_solution(day, profile).context("failed to call cargo")? {
+        return Ok(Outcome::BuildFailed);
     }
 
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    cmd_args.extend(profile.cargo_args().iter().map(ToString::to_string));
     cmd_args.push("--".to_string());
 
+    match mode {
+        Mode::Parts => {}
+        Mode::Loop { part, duration } => {
+            cmd_args.push("--loop-part".to_string());
+            cmd_args.push(part.to_string());
+            if let Some(duration) = duration {
+                cmd_args.push("--duration".to_string());
+                cmd_args.push(format!("{}ms", duration.as_millis()));
+            }
+        }
+        Mode::Visualize(dir) => {
+            cmd_args.push("--visualize".to_string());
+            cmd_args.push(dir.display().to_string());
+        }
+        Mode::Variants => cmd_args.push("--variants".to_string()),
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -20,12 +90,36 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if let Some(filter) = &trace.filter {
+        cmd_args.push("--trace".to_string());
+        cmd_args.push(filter.to_string());
+    }
+
+    for (flag, path) in [
+        ("--trace-out", &trace.out),
+        ("--trace-folded", &trace.folded),
+    ] {
+        if let Some(path) = path {
+            cmd_args.push(flag.to_string());
+            cmd_args.push(path.display().to_string());
+        }
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
-        .unwrap();
+        .context("failed to call cargo")?;
+
+    let status = wait_with_timeout(&mut cmd, timeout)
+        .with_context(|| format!("failed to wait for day {day}"))?;
 
-    cmd.wait().unwrap();
+    Ok(match status {
+        Some(status) => status.code().map_or(Outcome::Error, Outcome::from_code),
+        None => {
+            eprintln!("Day {day} timed out.");
+            Outcome::Timeout
+        }
+    })
 }
@@ -0,0 +1,172 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+
+use crate::template::error::{Context, Error};
+use crate::template::{answers, config, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+/// What exists for a day and how far it got.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayStatus {
+    pub day: u8,
+    /// `src/bin/DD.rs` exists.
+    pub bin: bool,
+    /// The input file exists and is not empty.
+    pub input: bool,
+    /// An example file (`DD.txt` or `DD-N.txt`) exists and is not empty.
+    pub examples: bool,
+    /// The puzzle description was downloaded.
+    pub puzzle: bool,
+    /// Whether the tests of the day pass, [`None`] if they were not run.
+    pub tests: Option<bool>,
+    /// The number of parts with a known answer.
+    pub answers: u8,
+    /// The number of stars earned by submitting answers.
+    pub stars: u8,
+}
+
+/// Prints the status of the given days, as a table or as JSON.
+/// Tests are run for every scaffolded day unless `run_tests` is `false`.
+pub fn handle(days: &[Day], json: bool, run_tests: bool) -> Result<(), Error> {
+    let statuses = days
+        .iter()
+        .map(|&day| read_status(day, run_tests))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if json {
+        let json = serde_json::to_string_pretty(&statuses).map_err(std::io::Error::from)?;
+        println!("{json}");
+    } else {
+        print!("{}", render(&statuses));
+    }
+
+    Ok(())
+}
+
+fn read_status(day: Day, run_tests: bool) -> Result<DayStatus, Error> {
+    let paths = &config::get().paths;
+    let bin = paths.bin(day).exists();
+    let answers = answers::read(day);
+
+    let tests = if bin && run_tests {
+        Some(run_day_tests(day).with_context(|| format!("failed to test day {day}"))?)
+    } else {
+        None
+    };
+
+    Ok(DayStatus {
+        day: day.into_inner(),
+        bin,
+        input: is_non_empty(&paths.input(day)),
+        examples: is_non_empty(&paths.example(day))
+            || (1..=2).any(|part| is_non_empty(&paths.examples.join(format!("{day}-{part}.txt")))),
+        puzzle: paths.puzzle(day).exists(),
+        tests,
+        answers: answers.count(),
+        stars: answers.stars,
+    })
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0)
+}
+
+/// Runs `cargo test` for the bin of a day, returns whether it passed.
+fn run_day_tests(day: Day) -> std::io::Result<bool> {
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+
+    Ok(status.success())
+}
+
+fn render(statuses: &[DayStatus]) -> String {
+    let check = |value: bool| if value { "✓" } else { "·" };
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}Day  Bin  Input  Examples  Puzzle  Tests  Answers  Stars{ANSI_RESET}"
+    )];
+
+    for status in statuses {
+        let tests = match status.tests {
+            Some(true) => "✓",
+            Some(false) => "✖",
+            None => "·",
+        };
+
+        lines.push(format!(
+            "{:02}   {}    {}      {}         {}       {}      {}/2      {}",
+            status.day,
+            check(status.bin),
+            check(status.input),
+            check(status.examples),
+            check(status.puzzle),
+            tests,
+            status.answers,
+            "★".repeat(status.stars.into()),
+        ));
+    }
+
+    lines.push(format!(
+        "\n{} of {} days scaffolded, {} stars.",
+        statuses.iter().filter(|status| status.bin).count(),
+        statuses.len(),
+        statuses
+            .iter()
+            .map(|status| u32::from(status.stars))
+            .sum::<u32>(),
+    ));
+
+    lines
+        .iter()
+        .map(|line| format!("{}\n", line.trim_end()))
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, DayStatus};
+
+    fn status(day: u8) -> DayStatus {
+        DayStatus {
+            day,
+            bin: true,
+            input: true,
+            examples: false,
+            puzzle: true,
+            tests: Some(day == 1),
+            answers: 2,
+            stars: day,
+        }
+    }
+
+    #[test]
+    fn renders_one_row_per_day() {
+        let output = render(&[status(1), status(2)]);
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(
+            lines[1],
+            "01   ✓    ✓      ·         ✓       ✓      2/2      ★"
+        );
+        assert_eq!(
+            lines[2],
+            "02   ✓    ✓      ·         ✓       ✖      2/2      ★★"
+        );
+        assert_eq!(lines[4], "2 of 2 days scaffolded, 3 stars.");
+    }
+
+    #[test]
+    fn serializes_to_json() {
+        let json = serde_json::to_string(&status(1)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"day":1,"bin":true,"input":true,"examples":false,"puzzle":true,"tests":true,"answers":2,"stars":1}"#
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use std::process::{Command, Stdio};
+
+use crate::template::error::{Context, Error};
+use crate::template::{config, outcome::Outcome, ANSI_BOLD, ANSI_RESET};
+use crate::Day;
+
+/// Runs `cargo test` for the bins of the given days, forwarding its output.
+/// Days that are not scaffolded are skipped. Returns [`Outcome::Error`] if the tests of a day failed.
+pub fn handle(days: &[Day]) -> Result<Outcome, Error> {
+    let mut failed: Vec<Day> = vec![];
+
+    for (i, &day) in days.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        if !config::get().paths.bin(day).exists() {
+            println!("Not solved.");
+            continue;
+        }
+
+        let status = Command::new("cargo")
+            .args(["test", "--quiet", "--bin", &day.to_string()])
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .context("failed to call cargo")?;
+
+        if !status.success() {
+            failed.push(day);
+        }
+    }
+
+    if failed.is_empty() {
+        return Ok(Outcome::Success);
+    }
+
+    println!("\n{ANSI_BOLD}Failures:{ANSI_RESET}");
+    for day in failed {
+        println!("Day {day}: tests failed");
+    }
+
+    Ok(Outcome::Error)
+}
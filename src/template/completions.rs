@@ -0,0 +1,399 @@
+/// Module that generates shell completion scripts from the commands in [`cli`].
+/// The scripts complete the cargo aliases of the template, e.g. `cargo solve <TAB>`, and leave every other cargo command to the completion that comes with cargo.
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use crate::all_days;
+use crate::template::cli::{Command, Flag, Value, COMMANDS, GLOBAL_FLAGS, SHELLS};
+use crate::template::day_set::KEYWORDS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(format!(
+                "unknown shell \"{s}\", expected one of: {}.",
+                SHELLS.join(", ")
+            )),
+        }
+    }
+}
+
+/// Generates the completion script for a shell.
+/// Scaffolded days are looked up in `bin_dir` when completing, relative to the working directory.
+#[must_use]
+pub fn generate(shell: Shell, bin_dir: &Path) -> String {
+    let bin_dir = bin_dir.display().to_string();
+
+    match shell {
+        Shell::Bash => bash(&quote(&bin_dir)),
+        Shell::Zsh => zsh(&quote(&bin_dir)),
+        Shell::Fish => fish(&quote_fish(&bin_dir)),
+    }
+}
+
+/// Quotes a string for bash and zsh.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Quotes a string for fish, which allows escaping quotes within single quotes.
+fn quote_fish(s: &str) -> String {
+    format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+fn days() -> String {
+    all_days().map(|day| day.to_string()).join(" ")
+}
+
+/// Days and the keywords of a [`DaySet`](crate::template::day_set::DaySet), ranges are left to the user.
+fn day_sets() -> String {
+    format!("{} {}", days(), KEYWORDS.join(" "))
+}
+
+fn flags(command: &Command) -> impl Iterator<Item = &Flag> {
+    command.flags.iter().chain(GLOBAL_FLAGS)
+}
+
+fn bash(bin_dir: &str) -> String {
+    let mut out = format!(
+        r#"# bash completion for the cargo aliases of this template.
+# Generated by `cargo completions bash`, load it with `source <(cargo completions bash)`.
+
+_advent_of_code_scaffolded_days() {{
+    local file days=()
+    for file in {bin_dir}/[0-9][0-9].rs; do
+        [[ -e $file ]] && days+=("$(basename "$file" .rs)")
+    done
+    compgen -W "${{days[*]}}" -- "$1"
+}}
+
+_advent_of_code_cargo_fallback() {{
+    if ! declare -F _cargo >/dev/null && declare -F __load_completion >/dev/null; then
+        __load_completion cargo
+        complete -F _advent_of_code_cargo cargo
+    fi
+    if declare -F _cargo >/dev/null; then
+        _cargo "$@"
+    fi
+}}
+
+_advent_of_code_cargo() {{
+    local cur=${{COMP_WORDS[COMP_CWORD]}} prev=${{COMP_WORDS[COMP_CWORD-1]}}
+    COMPREPLY=()
+
+    if [[ $COMP_CWORD -eq 1 ]]; then
+        _advent_of_code_cargo_fallback "$@"
+        COMPREPLY+=($(compgen -W "{}" -- "$cur"))
+        return
+    fi
+
+    case ${{COMP_WORDS[1]}} in
+"#,
+        COMMANDS.iter().map(|command| command.name).join(" ")
+    );
+
+    let words = |value: Value| match value {
+        Value::Day => Some(format!("compgen -W \"{}\" -- \"$cur\"", days())),
+        Value::Days | Value::OptionalDays => {
+            Some(format!("compgen -W \"{}\" -- \"$cur\"", day_sets()))
+        }
+        Value::ScaffoldedDay => Some("_advent_of_code_scaffolded_days \"$cur\"".into()),
+        Value::Part => Some("compgen -W \"1 2\" -- \"$cur\"".into()),
+        Value::Shell => Some(format!("compgen -W \"{}\" -- \"$cur\"", SHELLS.join(" "))),
+        Value::Path("dir") => Some("compgen -d -- \"$cur\"".into()),
+        Value::Path(_) => Some("compgen -f -- \"$cur\"".into()),
+        Value::Text(_) | Value::OptionalText(_) => None,
+    };
+
+    for command in COMMANDS {
+        writeln!(out, "        {})", command.name).unwrap();
+        writeln!(out, "            case $prev in").unwrap();
+        for flag in flags(command) {
+            let Some(value) = flag.value else { continue };
+            match (value, words(value)) {
+                (Value::OptionalText(_), _) => {}
+                (_, Some(words)) => writeln!(
+                    out,
+                    "                --{}) COMPREPLY=($({words})); return ;;",
+                    flag.name
+                )
+                .unwrap(),
+                (_, None) => writeln!(out, "                --{}) return ;;", flag.name).unwrap(),
+            }
+        }
+        writeln!(out, "            esac").unwrap();
+        writeln!(out, "            if [[ $cur == -* ]]; then").unwrap();
+        writeln!(
+            out,
+            "                COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            flags(command)
+                .map(|flag| format!("--{}", flag.name))
+                .join(" ")
+        )
+        .unwrap();
+        if let Some(words) = command.positional.and_then(words) {
+            writeln!(out, "            else").unwrap();
+            writeln!(out, "                COMPREPLY=($({words}))").unwrap();
+        }
+        writeln!(out, "            fi").unwrap();
+        writeln!(out, "            ;;").unwrap();
+    }
+
+    out.push_str(
+        r#"        *)
+            _advent_of_code_cargo_fallback "$@"
+            ;;
+    esac
+}
+
+complete -F _advent_of_code_cargo cargo
+"#,
+    );
+    out
+}
+
+fn zsh(bin_dir: &str) -> String {
+    let mut out = String::from(
+        r#"#compdef cargo
+# zsh completion for the cargo aliases of this template.
+# Generated by `cargo completions zsh`, load it with `source <(cargo completions zsh)` after `compinit`.
+
+_advent_of_code_cargo() {
+    local -a commands flags
+
+    if (( CURRENT == 2 )); then
+        commands=(
+"#,
+    );
+
+    for command in COMMANDS {
+        writeln!(
+            out,
+            "            {}",
+            quote(&format!("{}:{}", command.name, command.about))
+        )
+        .unwrap();
+    }
+
+    out.push_str(
+        r#"        )
+        _describe -t advent-of-code-commands 'advent of code command' commands
+        (( $+functions[_cargo] )) && _cargo "$@"
+        return 0
+    fi
+
+    case $words[2] in
+"#,
+    );
+
+    let words = |value: Value| match value {
+        Value::Day => Some(format!("compadd -- {}", days())),
+        Value::Days | Value::OptionalDays => Some(format!("compadd -- {}", day_sets())),
+        Value::ScaffoldedDay => Some(format!("compadd -- {bin_dir}/[0-9][0-9].rs(N:t:r)")),
+        Value::Part => Some("compadd -- 1 2".into()),
+        Value::Shell => Some(format!("compadd -- {}", SHELLS.join(" "))),
+        Value::Path("dir") => Some("_files -/".into()),
+        Value::Path(_) => Some("_files".into()),
+        Value::Text(_) | Value::OptionalText(_) => None,
+    };
+
+    for command in COMMANDS {
+        writeln!(out, "        {})", command.name).unwrap();
+        writeln!(out, "            case $words[CURRENT-1] in").unwrap();
+        for flag in flags(command) {
+            let Some(value) = flag.value else { continue };
+            match (value, words(value)) {
+                (Value::OptionalText(_), _) => {}
+                (_, Some(words)) => {
+                    writeln!(out, "                --{}) {words}; return 0 ;;", flag.name).unwrap()
+                }
+                (_, None) => writeln!(out, "                --{}) return 0 ;;", flag.name).unwrap(),
+            }
+        }
+        writeln!(out, "            esac").unwrap();
+        writeln!(out, "            flags=(").unwrap();
+        for flag in flags(command) {
+            writeln!(
+                out,
+                "                {}",
+                quote(&format!("--{}:{}", flag.name, flag.about))
+            )
+            .unwrap();
+        }
+        writeln!(out, "            )").unwrap();
+        writeln!(out, "            if [[ $PREFIX == -* ]]; then").unwrap();
+        writeln!(out, "                _describe -t flags 'flag' flags").unwrap();
+        if let Some(words) = command.positional.and_then(words) {
+            writeln!(out, "            else").unwrap();
+            writeln!(out, "                {words}").unwrap();
+        }
+        writeln!(out, "            fi").unwrap();
+        writeln!(out, "            ;;").unwrap();
+    }
+
+    out.push_str(
+        r#"        *)
+            (( $+functions[_cargo] )) && _cargo "$@"
+            ;;
+    esac
+}
+
+compdef _advent_of_code_cargo cargo
+"#,
+    );
+    out
+}
+
+fn fish(bin_dir: &str) -> String {
+    let mut out = format!(
+        r#"# fish completion for the cargo aliases of this template.
+# Generated by `cargo completions fish`, load it with `cargo completions fish | source`.
+
+function __advent_of_code_scaffolded_days
+    for file in {bin_dir}/[0-9][0-9].rs
+        basename $file .rs
+    end
+end
+
+"#
+    );
+
+    let candidates = |value: Value| match value {
+        Value::Day => Some(quote_fish(&days())),
+        Value::Days | Value::OptionalDays => Some(quote_fish(&day_sets())),
+        Value::ScaffoldedDay => Some("'(__advent_of_code_scaffolded_days)'".into()),
+        Value::Part => Some("'1 2'".into()),
+        Value::Shell => Some(quote_fish(&SHELLS.join(" "))),
+        Value::Path(_) | Value::Text(_) | Value::OptionalText(_) => None,
+    };
+
+    for command in COMMANDS {
+        writeln!(
+            out,
+            "complete -c cargo -n __fish_use_subcommand -a {} -d {}",
+            command.name,
+            quote_fish(command.about)
+        )
+        .unwrap();
+    }
+
+    for command in COMMANDS {
+        out.push('\n');
+        let condition = format!(
+            "complete -c cargo -n {}",
+            quote_fish(&format!("__fish_seen_subcommand_from {}", command.name))
+        );
+
+        match command.positional {
+            Some(Value::Path(_)) => {}
+            Some(value) => match candidates(value) {
+                Some(candidates) => writeln!(out, "{condition} -f -a {candidates}").unwrap(),
+                None => writeln!(out, "{condition} -f").unwrap(),
+            },
+            None => writeln!(out, "{condition} -f").unwrap(),
+        }
+
+        for flag in flags(command) {
+            let mut line = format!("{condition} -l {}", flag.name);
+            match flag.value {
+                Some(Value::Path("dir")) => line.push_str(" -x -a '(__fish_complete_directories)'"),
+                Some(Value::Path(_)) => line.push_str(" -r -F"),
+                Some(Value::OptionalText(_)) | None => {}
+                Some(value) => {
+                    line.push_str(" -x");
+                    if let Some(candidates) = candidates(value) {
+                        write!(line, " -a {candidates}").unwrap();
+                    }
+                }
+            }
+            writeln!(out, "{line} -d {}", quote_fish(flag.about)).unwrap();
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{generate, quote, quote_fish, Shell};
+
+    #[test]
+    fn parses_shell() {
+        assert_eq!("zsh".parse(), Ok(Shell::Zsh));
+        assert_eq!(
+            "powershell".parse::<Shell>(),
+            Err("unknown shell \"powershell\", expected one of: bash, zsh, fish.".into())
+        );
+    }
+
+    #[test]
+    fn quotes_for_all_shells() {
+        assert_eq!(quote("src/bin"), "'src/bin'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+        assert_eq!(quote_fish("it's"), r"'it\'s'");
+    }
+
+    #[test]
+    fn bash_completes_flags_and_days() {
+        let script = generate(Shell::Bash, Path::new("src/bin"));
+
+        assert!(script.contains("for file in 'src/bin'/[0-9][0-9].rs; do"));
+        assert!(script.contains("compgen -W \"scaffold download read solve all"));
+        assert!(script.contains(
+            "                --submit) COMPREPLY=($(compgen -W \"1 2\" -- \"$cur\")); return ;;"
+        ));
+        assert!(script.contains("                --timeout) return ;;"));
+        assert!(!script.contains("--trace)"));
+        assert!(script
+            .contains("                COMPREPLY=($(_advent_of_code_scaffolded_days \"$cur\"))"));
+        assert!(script.contains("compgen -W \"01 02 03"));
+        assert!(script.contains(" 24 25\" -- \"$cur\""));
+    }
+
+    #[test]
+    fn zsh_describes_commands_and_flags() {
+        let script = generate(Shell::Zsh, Path::new("src/bin"));
+
+        assert!(script.starts_with("#compdef cargo\n"));
+        assert!(script.contains("            'solve:Run the solution of a day'\n"));
+        assert!(script.contains("                '--no-tests:Do not run the tests of each day'\n"));
+        assert!(script.contains("                compadd -- 'src/bin'/[0-9][0-9].rs(N:t:r)\n"));
+        assert!(script.contains("                --trace-out) _files; return 0 ;;\n"));
+    }
+
+    #[test]
+    fn fish_completes_per_subcommand() {
+        let script = generate(Shell::Fish, Path::new("src/bin"));
+
+        assert!(script.contains(
+            "complete -c cargo -n __fish_use_subcommand -a calendar -d 'Draw the advent calendar with the stars of each day'\n"
+        ));
+        assert!(script.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from solve' -f -a '(__advent_of_code_scaffolded_days)'\n"
+        ));
+        assert!(script.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from solve' -l submit -x -a '1 2' -d 'Submit the answer of a part'\n"
+        ));
+        assert!(script.contains(
+            "complete -c cargo -n '__fish_seen_subcommand_from solve' -l trace -d 'Log tracing events, filtered like RUST_LOG'\n"
+        ));
+        assert!(script.contains("complete -c cargo -n '__fish_seen_subcommand_from progress' -f\n"));
+    }
+}
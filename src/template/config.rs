@@ -0,0 +1,429 @@
+/// Project settings, loaded once from `aoc.toml` in the working directory.
+/// Every setting has a default, so the file and each of its keys are optional.
+/// Settings can be overridden with environment variables, which the CLI sets for its flags so they reach solution binaries as well.
+///
+/// ```toml
+/// year = 2023
+/// auto_download = false
+///
+/// [paths]
+/// inputs = "data/inputs"
+/// examples = "data/examples"
+/// puzzles = "data/puzzles"
+/// answers = "data/answers"
+/// bin = "src/bin"
+///
+/// [bench]
+/// target_time = "1s"
+/// min_samples = 10
+/// max_samples = 10000
+///
+/// [defaults]
+/// release = false
+/// time = false
+/// timeout = "30s"
+/// ```
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs};
+
+use serde::{Deserialize, Deserializer};
+
+use crate::template::error::{self, Context, Error};
+use crate::Day;
+
+/// Environment variable that points to a config file other than `aoc.toml`.
+pub const CONFIG_PATH_VAR: &str = "AOC_CONFIG";
+
+/// Environment variables that override a setting, with the setting they override.
+pub const ENV_OVERRIDES: [(&str, &str); 11] = [
+    ("AOC_YEAR", "year"),
+    ("AOC_AUTO_DOWNLOAD", "auto_download"),
+    ("AOC_INPUTS_DIR", "paths.inputs"),
+    ("AOC_EXAMPLES_DIR", "paths.examples"),
+    ("AOC_PUZZLES_DIR", "paths.puzzles"),
+    ("AOC_ANSWERS_DIR", "paths.answers"),
+    ("AOC_BIN_DIR", "paths.bin"),
+    ("AOC_BENCH_TARGET_TIME", "bench.target_time"),
+    ("AOC_BENCH_MIN_SAMPLES", "bench.min_samples"),
+    ("AOC_BENCH_MAX_SAMPLES", "bench.max_samples"),
+    ("AOC_TIMEOUT", "defaults.timeout"),
+];
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The year of advent passed to aoc-cli. aoc-cli picks the current (or last) year if unset.
+    pub year: Option<u16>,
+    /// Download missing or empty inputs when running a solution, if an aoc-cli session is configured.
+    pub auto_download: bool,
+    pub paths: Paths,
+    pub bench: Bench,
+    pub defaults: Defaults,
+}
+
+/// Locations of the files that make up a day.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Paths {
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    pub answers: PathBuf,
+    pub bin: PathBuf,
+}
+
+/// Settings for `--time`: the runner repeats a part until `target_time` is reached, within the bounds of the sample counts.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Bench {
+    #[serde(deserialize_with = "deserialize_duration")]
+    pub target_time: Duration,
+    pub min_samples: u64,
+    pub max_samples: u64,
+}
+
+/// Flags that are enabled for `solve` and `all` even if they are not passed.
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    pub release: bool,
+    pub time: bool,
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    pub timeout: Option<Duration>,
+}
+
+impl Default for Paths {
+    fn default() -> Self {
+        Self {
+            inputs: "data/inputs".into(),
+            examples: "data/examples".into(),
+            puzzles: "data/puzzles".into(),
+            answers: "data/answers".into(),
+            bin: "src/bin".into(),
+        }
+    }
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            target_time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+impl Bench {
+    /// Checks that a benchmark takes at least one sample, the average of no samples is undefined.
+    fn validate(&self) -> Result<(), Error> {
+        for (key, samples) in [
+            ("bench.min_samples", self.min_samples),
+            ("bench.max_samples", self.max_samples),
+        ] {
+            if samples == 0 {
+                return Err(Error::InvalidArguments(format!("{key} must be at least 1")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Paths {
+    /// Returns the directory of a data folder, e.g. `inputs` or `examples`.
+    /// Folders without a setting live in `data/`.
+    #[must_use]
+    pub fn folder(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.inputs.clone(),
+            "examples" => self.examples.clone(),
+            "puzzles" => self.puzzles.clone(),
+            "answers" => self.answers.clone(),
+            other => Path::new("data").join(other),
+        }
+    }
+
+    #[must_use]
+    pub fn input(&self, day: Day) -> PathBuf {
+        self.inputs.join(format!("{day}.txt"))
+    }
+
+    #[must_use]
+    pub fn example(&self, day: Day) -> PathBuf {
+        self.examples.join(format!("{day}.txt"))
+    }
+
+    #[must_use]
+    pub fn puzzle(&self, day: Day) -> PathBuf {
+        self.puzzles.join(format!("{day}.md"))
+    }
+
+    #[must_use]
+    pub fn answers(&self, day: Day) -> PathBuf {
+        self.answers.join(format!("{day}.txt"))
+    }
+
+    #[must_use]
+    pub fn bin(&self, day: Day) -> PathBuf {
+        self.bin.join(format!("{day}.rs"))
+    }
+}
+
+impl Config {
+    /// Parses the contents of a config file.
+    pub fn from_toml(content: &str) -> Result<Self, Error> {
+        let config: Self = toml::from_str(content).map_err(|e| Error::Config(e.to_string()))?;
+        config.bench.validate()?;
+        Ok(config)
+    }
+
+    /// Loads the config file (if there is one) and applies environment variable overrides.
+    pub fn load() -> Result<Self, Error> {
+        let path = env::var(CONFIG_PATH_VAR).unwrap_or_else(|_| "aoc.toml".into());
+
+        let mut config = match fs::read_to_string(&path) {
+            Ok(content) => {
+                Self::from_toml(&content).with_context(|| format!("failed to parse \"{path}\""))?
+            }
+            Err(e)
+                if e.kind() == std::io::ErrorKind::NotFound
+                    && env::var(CONFIG_PATH_VAR).is_err() =>
+            {
+                Self::default()
+            }
+            Err(e) => return Err(e).with_context(|| format!("failed to read \"{path}\"")),
+        };
+
+        config.apply_overrides(|var| env::var(var).ok())?;
+        Ok(config)
+    }
+
+    /// Overrides settings with the values returned by `lookup` for the variables in [`ENV_OVERRIDES`].
+    pub fn apply_overrides(
+        &mut self,
+        lookup: impl Fn(&str) -> Option<String>,
+    ) -> Result<(), Error> {
+        for (var, _) in ENV_OVERRIDES {
+            let Some(value) = lookup(var).filter(|value| !value.is_empty()) else {
+                continue;
+            };
+
+            let invalid = || Error::Config(format!("invalid value \"{value}\" for {var}"));
+            let invalid_duration =
+                |e: String| Error::Config(format!("invalid value \"{value}\" for {var}: {e}"));
+
+            match var {
+                "AOC_YEAR" => self.year = Some(value.parse().map_err(|_| invalid())?),
+                "AOC_AUTO_DOWNLOAD" => self.auto_download = value.parse().map_err(|_| invalid())?,
+                "AOC_INPUTS_DIR" => self.paths.inputs = value.into(),
+                "AOC_EXAMPLES_DIR" => self.paths.examples = value.into(),
+                "AOC_PUZZLES_DIR" => self.paths.puzzles = value.into(),
+                "AOC_ANSWERS_DIR" => self.paths.answers = value.into(),
+                "AOC_BIN_DIR" => self.paths.bin = value.into(),
+                "AOC_BENCH_TARGET_TIME" => {
+                    self.bench.target_time = parse_duration(&value).map_err(invalid_duration)?;
+                }
+                "AOC_BENCH_MIN_SAMPLES" => {
+                    self.bench.min_samples = value.parse().map_err(|_| invalid())?;
+                }
+                "AOC_BENCH_MAX_SAMPLES" => {
+                    self.bench.max_samples = value.parse().map_err(|_| invalid())?;
+                }
+                "AOC_TIMEOUT" => {
+                    self.defaults.timeout = Some(parse_duration(&value).map_err(invalid_duration)?);
+                }
+                _ => {}
+            }
+        }
+
+        self.bench.validate()
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Loads the config and keeps it for the rest of the process.
+/// Call this early to surface errors in the config file, later calls return the stored config.
+pub fn init() -> Result<&'static Config, Error> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let config = Config::load()?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Returns the config, loading it on first use.
+/// If the config cannot be loaded, the error is printed and the defaults are used.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            error::report(&e);
+            Config::default()
+        })
+    })
+}
+
+/// Parses a duration like `500ms`, `10s` or `2m`. A plain number is read as seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (value, unit) = match s.find(|c: char| !c.is_ascii_digit() && c != '.') {
+        Some(index) => s.split_at(index),
+        None => (s, "s"),
+    };
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}`"))?;
+
+    let seconds = match unit {
+        "ms" => value / 1000.0,
+        "s" => value,
+        "m" => value * 60.0,
+        _ => {
+            return Err(format!(
+                "invalid duration unit in `{s}`, expecting ms, s or m"
+            ))
+        }
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("duration `{s}` is out of range"))
+}
+
+fn deserialize_duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    let s = String::deserialize(deserializer)?;
+    parse_duration(&s).map_err(serde::de::Error::custom)
+}
+
+fn deserialize_optional_duration<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    deserialize_duration(deserializer).map(Some)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_duration, Config};
+    use crate::day;
+    use std::{path::PathBuf, time::Duration};
+
+    #[test]
+    fn defaults_without_file() {
+        let config = Config::from_toml("").unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.year, None);
+        assert_eq!(
+            config.paths.input(day!(1)),
+            PathBuf::from("data/inputs/01.txt")
+        );
+        assert_eq!(config.bench.target_time, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn parses_partial_file() {
+        let config = Config::from_toml(
+            "year = 2022\n[paths]\ninputs = \"inputs\"\n[bench]\ntarget_time = \"250ms\"\n[defaults]\ntimeout = \"10s\"",
+        )
+        .unwrap();
+        assert_eq!(config.year, Some(2022));
+        assert_eq!(config.paths.input(day!(7)), PathBuf::from("inputs/07.txt"));
+        assert_eq!(config.paths.bin(day!(7)), PathBuf::from("src/bin/07.rs"));
+        assert_eq!(config.bench.target_time, Duration::from_millis(250));
+        assert_eq!(config.bench.max_samples, 10000);
+        assert_eq!(config.defaults.timeout, Some(Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn parses_shipped_config() {
+        let config = Config::from_toml(include_str!("../../aoc.toml")).unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.paths, Config::default().paths);
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Config::from_toml("[paths]\ninput = \"x\"").is_err());
+        assert!(Config::from_toml("[bench]\ntarget_time = \"1h\"").is_err());
+    }
+
+    #[test]
+    fn environment_overrides_file() {
+        let mut config = Config::from_toml("year = 2022").unwrap();
+        config
+            .apply_overrides(|var| match var {
+                "AOC_YEAR" => Some("2021".into()),
+                "AOC_AUTO_DOWNLOAD" => Some("true".into()),
+                "AOC_EXAMPLES_DIR" => Some("tests/examples".into()),
+                "AOC_BENCH_MIN_SAMPLES" => Some("3".into()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(config.year, Some(2021));
+        assert!(config.auto_download);
+        assert_eq!(
+            config.paths.example(day!(2)),
+            PathBuf::from("tests/examples/02.txt")
+        );
+        assert_eq!(config.bench.min_samples, 3);
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        let mut config = Config::default();
+        let result = config.apply_overrides(|var| (var == "AOC_YEAR").then(|| "soon".into()));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_duration("3"), Ok(Duration::from_secs(3)));
+        assert!(parse_duration("3h").is_err());
+        assert!(parse_duration("s").is_err());
+        assert!(parse_duration("1e30").is_err());
+        assert!(parse_duration(&"9".repeat(40)).is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_durations() {
+        let huge = "9".repeat(40);
+
+        let error = Config::from_toml(&format!("[defaults]\ntimeout = \"{huge}\""))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("timeout"), "{error}");
+        assert!(error.contains("out of range"), "{error}");
+
+        let mut config = Config::default();
+        let error = config
+            .apply_overrides(|var| (var == "AOC_TIMEOUT").then(|| huge.clone()))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("AOC_TIMEOUT"), "{error}");
+        assert!(error.contains("out of range"), "{error}");
+    }
+
+    #[test]
+    fn rejects_zero_samples() {
+        let error = Config::from_toml("[bench]\nmin_samples = 0")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("bench.min_samples"), "{error}");
+
+        let error = Config::from_toml("[bench]\nmax_samples = 0")
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("bench.max_samples"), "{error}");
+
+        let mut config = Config::default();
+        let error = config
+            .apply_overrides(|var| (var == "AOC_BENCH_MIN_SAMPLES").then(|| "0".into()))
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("bench.min_samples"), "{error}");
+    }
+}
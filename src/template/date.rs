@@ -0,0 +1,134 @@
+/// Minimal calendar dates, enough to tell the current advent day without pulling in a date library.
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Puzzles unlock at midnight in UTC-5, so advent dates are taken in that timezone.
+const UNLOCK_OFFSET_SECONDS: i64 = -5 * 60 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: i64,
+    pub month: u8,
+    pub day: u8,
+}
+
+impl Date {
+    /// Converts a unix timestamp to a date in UTC.
+    #[must_use]
+    pub fn from_unix(timestamp: i64) -> Self {
+        // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = timestamp.div_euclid(86400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Self {
+            year,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    /// Returns the current date in the timezone in which puzzles unlock.
+    #[must_use]
+    pub fn today() -> Self {
+        Self::from_unix(now_unix() + UNLOCK_OFFSET_SECONDS)
+    }
+
+    /// Returns the advent day of this date, if it falls between December 1st and 25th.
+    #[must_use]
+    pub fn advent_day(&self) -> Option<u8> {
+        (self.month == 12 && self.day <= 25).then_some(self.day)
+    }
+
+    /// Returns the year of the most recent event: the current year in December, the previous year otherwise.
+    #[must_use]
+    pub fn latest_event(&self) -> i64 {
+        if self.month == 12 {
+            self.year
+        } else {
+            self.year - 1
+        }
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Returns the unix timestamp at which the puzzle of a day unlocks.
+#[must_use]
+pub fn unlock_time(year: i64, day: u8) -> i64 {
+    // days since 1970-01-01 for December 1st of `year`, see `days_from_civil` in the link above.
+    let y = year;
+    let era = y.div_euclid(400);
+    let year_of_era = y.rem_euclid(400);
+    let day_of_year = (153 * 9 + 2) / 5;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let december_first = era * 146_097 + day_of_era - 719_468;
+
+    (december_first + i64::from(day) - 1) * 86400 - UNLOCK_OFFSET_SECONDS
+}
+
+/// Returns the current unix timestamp.
+#[must_use]
+pub fn now_unix() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| {
+            i64::try_from(duration.as_secs()).unwrap_or(i64::MAX)
+        })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{unlock_time, Date};
+
+    #[test]
+    fn converts_timestamps() {
+        assert_eq!(
+            Date::from_unix(0),
+            Date {
+                year: 1970,
+                month: 1,
+                day: 1
+            }
+        );
+        assert_eq!(Date::from_unix(1_701_406_800).to_string(), "2023-12-01");
+        assert_eq!(Date::from_unix(1_709_164_800).to_string(), "2024-02-29");
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(2023, 1), 1_701_406_800);
+        assert_eq!(unlock_time(2023, 25), 1_701_406_800 + 24 * 86400);
+    }
+
+    #[test]
+    fn finds_advent_days() {
+        let date = |month, day| Date {
+            year: 2023,
+            month,
+            day,
+        };
+        assert_eq!(date(12, 5).advent_day(), Some(5));
+        assert_eq!(date(12, 26).advent_day(), None);
+        assert_eq!(date(11, 5).advent_day(), None);
+        assert_eq!(date(12, 26).latest_event(), 2023);
+        assert_eq!(date(6, 1).latest_event(), 2022);
+    }
+}
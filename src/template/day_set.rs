@@ -0,0 +1,191 @@
+/// Module that parses a selection of days like `3..7,odd`, for the commands that work on several days.
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{answers, config};
+use crate::{all_days, all_days_in, Day};
+
+/// The keywords that can be used in a [`DaySet`], next to day numbers and ranges.
+pub const KEYWORDS: [&str; 5] = ["all", "odd", "even", "last", "unsolved"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selector {
+    Day(Day),
+    /// Both ends are included.
+    Range(Day, Day),
+    All,
+    Odd,
+    Even,
+    /// The latest day that has a solution.
+    Last,
+    /// Days that have a solution, but not two stars or known answers yet.
+    Unsolved,
+}
+
+/// A selection of days, parsed from a comma separated list of:
+/// - day numbers, e.g. `3`
+/// - ranges that include both ends, e.g. `3..7`, `3..=7`, `20..` or `..5`. Unlike a Rust range, `3..7` includes
+///   day 7, like [`all_days_in`]
+/// - `all`, `odd` or `even`
+/// - `last`, the latest day that has a solution
+/// - `unsolved`, days that have a solution but not two stars or known answers yet
+///
+/// ```
+/// # use advent_of_code::template::day_set::DaySet;
+/// let set: DaySet = "1,4..6,odd".parse().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySet(Vec<Selector>);
+
+impl DaySet {
+    /// Selects every day of advent.
+    #[must_use]
+    pub fn all() -> Self {
+        Self(vec![Selector::All])
+    }
+
+    /// Returns the selected days in order, reading which days are scaffolded and solved if needed.
+    #[must_use]
+    pub fn days(&self) -> Vec<Day> {
+        let paths = &config::get().paths;
+        self.select(
+            |day| paths.bin(day).exists(),
+            |day| answers::read(day).progress() == 2,
+        )
+    }
+
+    fn select(
+        &self,
+        is_scaffolded: impl Fn(Day) -> bool,
+        is_solved: impl Fn(Day) -> bool,
+    ) -> Vec<Day> {
+        let mut days = BTreeSet::new();
+
+        for selector in &self.0 {
+            match *selector {
+                Selector::Day(day) => {
+                    days.insert(day);
+                }
+                Selector::Range(first, last) => days.extend(all_days_in(first..=last)),
+                Selector::All => days.extend(all_days()),
+                Selector::Odd => days.extend(all_days().filter(|day| day.into_inner() % 2 == 1)),
+                Selector::Even => days.extend(all_days().filter(|day| day.into_inner() % 2 == 0)),
+                Selector::Last => days.extend(all_days().filter(|day| is_scaffolded(*day)).last()),
+                Selector::Unsolved => {
+                    days.extend(all_days().filter(|day| is_scaffolded(*day) && !is_solved(*day)))
+                }
+            }
+        }
+
+        days.into_iter().collect()
+    }
+}
+
+impl Default for DaySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(|part| {
+                parse_selector(part.trim()).ok_or_else(|| DaySetFromStrError(part.trim().into()))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+fn parse_selector(s: &str) -> Option<Selector> {
+    match s.to_ascii_lowercase().as_str() {
+        "all" => return Some(Selector::All),
+        "odd" => return Some(Selector::Odd),
+        "even" => return Some(Selector::Even),
+        "last" => return Some(Selector::Last),
+        "unsolved" => return Some(Selector::Unsolved),
+        _ => {}
+    }
+
+    let Some((first, last)) = s.split_once("..") else {
+        return s.parse().ok().map(Selector::Day);
+    };
+
+    let parse_bound = |s: &str, unbounded: u8| match s.trim() {
+        "" => Day::new(unbounded),
+        s => s.parse().ok(),
+    };
+
+    let first = parse_bound(first, 1)?;
+    let last = parse_bound(last.strip_prefix('=').unwrap_or(last), 25)?;
+    (first <= last).then_some(Selector::Range(first, last))
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection \"{}\", expecting a day, a range like 3..7 or one of: {}",
+            self.0,
+            KEYWORDS.join(", ")
+        )
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::Day;
+
+    fn select(s: &str) -> Vec<u8> {
+        let set: DaySet = s.parse().unwrap();
+        set.select(|day| day <= 5, |day| day == 2 || day == 4)
+            .into_iter()
+            .map(Day::into_inner)
+            .collect()
+    }
+
+    #[test]
+    fn parses_days_and_ranges() {
+        assert_eq!(select("3"), vec![3]);
+        assert_eq!(select("03"), vec![3]);
+        assert_eq!(select("3..7"), vec![3, 4, 5, 6, 7]);
+        assert_eq!(select("3..=7"), vec![3, 4, 5, 6, 7]);
+        assert_eq!(select("23.."), vec![23, 24, 25]);
+        assert_eq!(select("..2"), vec![1, 2]);
+        assert_eq!(select("9,1, 4"), vec![1, 4, 9]);
+        assert_eq!(select("1..3,2..4"), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(select("all").len(), 25);
+        assert_eq!(select("odd").len(), 13);
+        assert_eq!(select("even,odd"), select("all"));
+        assert_eq!(select("last"), vec![5]);
+        assert_eq!(select("unsolved"), vec![1, 3, 5]);
+        assert_eq!(select("Last,20"), vec![5, 20]);
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        for s in ["", "0", "26", "7..3", "1..26", "1,", "first", "1...3"] {
+            assert!(s.parse::<DaySet>().is_err(), "{s} should be invalid");
+        }
+        assert_eq!(
+            "1,x".parse::<DaySet>().unwrap_err().to_string(),
+            "invalid day selection \"x\", expecting a day, a range like 3..7 or one of: all, odd, even, last, unsolved"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+/// The error type shared by all template commands.
+/// Errors can be wrapped with context messages, the resulting chain is rendered once by [`report`].
+use std::fmt::Display;
+use std::io;
+use std::path::PathBuf;
+use std::process::ExitStatus;
+
+#[derive(Debug)]
+pub enum Error {
+    /// The `aoc` command is not installed or not callable.
+    AocCliNotFound,
+    /// The `aoc` command exited with a non-zero status.
+    AocCliFailed(ExitStatus),
+    /// The stdout or stderr of a child process could not be captured.
+    BrokenPipe,
+    /// The command-line input could not be understood.
+    InvalidArguments(String),
+    /// The benchmark table markers in the readme are missing or ambiguous.
+    Readme(String),
+    /// The config file or an environment variable override is invalid.
+    Config(String),
+    /// A private leaderboard could not be fetched or read.
+    Leaderboard(String),
+    /// A data file of a day does not exist. The hint suggests how to create it.
+    MissingFile {
+        path: PathBuf,
+        hint: Option<String>,
+    },
+    /// A data file of a day exists, but is empty.
+    EmptyFile {
+        path: PathBuf,
+        hint: Option<String>,
+    },
+    Io(io::Error),
+    /// Another error with a message describing what was being done when it occurred.
+    Context {
+        message: String,
+        source: Box<Error>,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::AocCliNotFound => write!(
+                f,
+                "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+            ),
+            Error::AocCliFailed(status) => write!(f, "aoc-cli exited with {status}."),
+            Error::BrokenPipe => write!(f, "could not capture the output of a child process."),
+            Error::InvalidArguments(message)
+            | Error::Readme(message)
+            | Error::Config(message)
+            | Error::Leaderboard(message) => write!(f, "{message}"),
+            Error::MissingFile { path, hint } => {
+                write!(f, "could not find \"{}\".", path.display())?;
+                hint.iter().try_for_each(|hint| write!(f, " {hint}"))
+            }
+            Error::EmptyFile { path, hint } => {
+                write!(f, "\"{}\" is empty.", path.display())?;
+                hint.iter().try_for_each(|hint| write!(f, " {hint}"))
+            }
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Context { message, .. } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+/// Adds a context message to the error of a [`Result`].
+pub trait Context<T> {
+    /// Wraps the error with `message`.
+    fn context(self, message: impl Into<String>) -> Result<T, Error>;
+
+    /// Wraps the error with a lazily built message.
+    fn with_context<M: Into<String>>(self, message: impl FnOnce() -> M) -> Result<T, Error>;
+}
+
+impl<T, E: Into<Error>> Context<T> for Result<T, E> {
+    fn context(self, message: impl Into<String>) -> Result<T, Error> {
+        self.with_context(|| message)
+    }
+
+    fn with_context<M: Into<String>>(self, message: impl FnOnce() -> M) -> Result<T, Error> {
+        self.map_err(|e| Error::Context {
+            message: message().into(),
+            source: Box::new(e.into()),
+        })
+    }
+}
+
+/// Renders an error and every error that caused it, one per line.
+#[must_use]
+pub fn render(error: &Error) -> String {
+    let mut lines = vec![format!("Error: {error}")];
+    let mut source = std::error::Error::source(error);
+
+    while let Some(cause) = source {
+        lines.push(format!("  caused by: {cause}"));
+        source = cause.source();
+    }
+
+    lines.join("\n")
+}
+
+/// Prints an error and its causes to stderr.
+pub fn report(error: &Error) {
+    eprintln!("{}", render(error));
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Context, Error};
+    use std::io;
+
+    #[test]
+    fn renders_context_chain() {
+        let result: Result<(), io::Error> = Err(io::Error::new(io::ErrorKind::NotFound, "gone"));
+        let error = result
+            .context("failed to read input")
+            .with_context(|| format!("failed to solve day {}", 1))
+            .unwrap_err();
+
+        assert_eq!(
+            render(&error),
+            [
+                "Error: failed to solve day 1",
+                "  caused by: failed to read input",
+                "  caused by: gone",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_single_error() {
+        assert_eq!(
+            render(&Error::InvalidArguments("unknown part 3".into())),
+            "Error: unknown part 3"
+        );
+    }
+
+    #[test]
+    fn renders_missing_file_with_hint() {
+        let error = Error::MissingFile {
+            path: "data/inputs/05.txt".into(),
+            hint: Some("Run `cargo download 05` to download it.".into()),
+        };
+        assert_eq!(
+            render(&error),
+            "Error: could not find \"data/inputs/05.txt\". Run `cargo download 05` to download it."
+        );
+    }
+}
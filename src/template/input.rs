@@ -0,0 +1,145 @@
+/// Normalization of puzzle inputs before they are passed to a solution.
+/// Line endings are always normalized, everything else is opt-in per day via the `solution!` macro, e.g.:
+/// ```ignore
+/// advent_of_code::solution!(2, trim = true, strict = true);
+/// ```
+use std::fmt::Display;
+
+/// How the input of a day is normalized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InputOptions {
+    /// Remove trailing whitespace from every line and the end of the input, including the final newline.
+    pub trim: bool,
+    /// Warn about characters that parsers rarely expect: tabs and non-ASCII characters.
+    pub strict: bool,
+}
+
+impl InputOptions {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            trim: false,
+            strict: false,
+        }
+    }
+
+    #[must_use]
+    pub const fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    #[must_use]
+    pub const fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+}
+
+/// A character found by a strict input check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Warning {
+    /// 1-based line of the first occurrence.
+    pub line: usize,
+    /// 1-based column (in characters) of the first occurrence.
+    pub column: usize,
+    pub char: char,
+    /// Total number of occurrences of this kind of character.
+    pub count: usize,
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = if self.char == '\t' {
+            "tab character".to_string()
+        } else {
+            format!("non-ASCII character {:?}", self.char)
+        };
+
+        write!(f, "{}:{}: {kind}", self.line, self.column)?;
+
+        if self.count > 1 {
+            write!(f, " ({} occurrences)", self.count)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Converts `\r\n` line endings to `\n`. With `trim`, trailing whitespace of every line and of the input is removed.
+#[must_use]
+pub fn normalize(input: &str, options: InputOptions) -> String {
+    let input = input.replace("\r\n", "\n");
+
+    if !options.trim {
+        return input;
+    }
+
+    input
+        .trim_end()
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Finds tabs and non-ASCII characters. Returns the first occurrence of tabs and of non-ASCII characters.
+#[must_use]
+pub fn check(input: &str) -> Vec<Warning> {
+    let mut tabs: Option<Warning> = None;
+    let mut non_ascii: Option<Warning> = None;
+
+    for (line, content) in input.lines().enumerate() {
+        for (column, char) in content.chars().enumerate() {
+            let slot = match char {
+                '\t' => &mut tabs,
+                c if !c.is_ascii() => &mut non_ascii,
+                _ => continue,
+            };
+
+            match slot {
+                Some(warning) => warning.count += 1,
+                None => {
+                    *slot = Some(Warning {
+                        line: line + 1,
+                        column: column + 1,
+                        char,
+                        count: 1,
+                    });
+                }
+            }
+        }
+    }
+
+    tabs.into_iter().chain(non_ascii).collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, normalize, InputOptions};
+
+    #[test]
+    fn normalizes_line_endings() {
+        let options = InputOptions::new();
+        assert_eq!(normalize("a\r\nb \r\n\r\n", options), "a\nb \n\n");
+    }
+
+    #[test]
+    fn trims_trailing_whitespace() {
+        let options = InputOptions::new().trim(true);
+        assert_eq!(normalize("a \r\n  b\t\n\n \n", options), "a\n  b");
+        assert_eq!(normalize("\n\n", options), "");
+    }
+
+    #[test]
+    fn finds_tabs_and_non_ascii() {
+        let warnings = check("ab\tc\n→x\t\n");
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(
+            warnings[0].to_string(),
+            "1:3: tab character (2 occurrences)"
+        );
+        assert_eq!(warnings[1].to_string(), "2:1: non-ASCII character '→'");
+        assert!(check("plain input\n").is_empty());
+    }
+}
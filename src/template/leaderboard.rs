@@ -0,0 +1,268 @@
+/// Private leaderboards, in the JSON format served by `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+/// Fetched leaderboards are cached in `data/leaderboards/`, the previous fetch is kept next to it to show what changed.
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde::Deserialize;
+
+use crate::template::date::unlock_time;
+use crate::template::error::Error;
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Stars by day and part.
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<u8, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    /// Unix timestamp at which the star was earned.
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    /// Parses the JSON served by the private leaderboard API.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        serde_json::from_str(json)
+            .map_err(|e| Error::Leaderboard(format!("invalid leaderboard JSON: {e}")))
+    }
+
+    /// Returns the members ordered by local score (then stars), like the leaderboard on the website.
+    #[must_use]
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+
+    fn year(&self) -> i64 {
+        self.event.parse().unwrap_or_default()
+    }
+}
+
+impl Member {
+    /// The name shown on the website, anonymous users are shown with their id.
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    #[must_use]
+    pub fn star(&self, day: u8, part: u8) -> Option<&Star> {
+        self.completion_day_level.get(&day)?.get(&part)
+    }
+}
+
+/// Renders the ranking and the star times for every day, marking what changed since `previous`.
+#[must_use]
+pub fn render(id: &str, current: &Leaderboard, previous: Option<&Leaderboard>) -> String {
+    let mut out = String::new();
+    let ranking = current.ranking();
+
+    let _ = writeln!(
+        out,
+        "{ANSI_BOLD}Leaderboard {id} ({}){ANSI_RESET}, {} members",
+        current.event,
+        ranking.len()
+    );
+
+    let previous_member =
+        |member: &Member| previous.and_then(|p| p.members.get(&member.id.to_string()));
+    let previous_rank =
+        |member: &Member| previous.and_then(|p| p.ranking().iter().position(|m| m.id == member.id));
+
+    let _ = writeln!(out, "\n{:>4}  {:<12}  {:<10}  Name", "#", "Score", "Stars");
+
+    for (rank, member) in ranking.iter().enumerate() {
+        let before = previous_member(member);
+        let score_delta = before.map_or(0, |before| {
+            member.local_score.saturating_sub(before.local_score)
+        });
+        let stars_delta = before.map_or(0, |before| member.stars.saturating_sub(before.stars));
+
+        let rank_change = match previous_rank(member) {
+            Some(before) if before > rank => format!(" ↑{}", before - rank),
+            Some(before) if before < rank => format!(" ↓{}", rank - before),
+            None if previous.is_some() => " (new)".into(),
+            _ => String::new(),
+        };
+
+        let _ = writeln!(
+            out,
+            "{:>4}  {:<12}  {:<10}  {}{rank_change}",
+            rank + 1,
+            with_delta(member.local_score, score_delta),
+            with_delta(member.stars.into(), stars_delta.into()),
+            member.display_name(),
+        );
+    }
+
+    let last_day = ranking
+        .iter()
+        .flat_map(|member| member.completion_day_level.keys())
+        .max()
+        .copied()
+        .unwrap_or(0);
+
+    for day in (1..=last_day).rev() {
+        let mut finishers: Vec<(&Member, Option<&Star>, Option<&Star>)> = ranking
+            .iter()
+            .filter_map(|member| {
+                let first = member.star(day, 1)?;
+                Some((*member, Some(first), member.star(day, 2)))
+            })
+            .collect();
+
+        finishers.sort_by_key(|(member, first, second)| {
+            (
+                second.map_or(i64::MAX, |star| star.get_star_ts),
+                first.map_or(i64::MAX, |star| star.get_star_ts),
+                member.id,
+            )
+        });
+
+        let _ = writeln!(out, "\n{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        let unlock = unlock_time(current.year(), day);
+
+        for (member, first, second) in finishers {
+            let before = previous_member(member);
+            let is_new =
+                |part: u8| previous.is_some() && before.and_then(|b| b.star(day, part)).is_none();
+
+            let mut line = format!("  {:<24}", member.display_name());
+            for (part, star) in [(1, first), (2, second)] {
+                let Some(star) = star else {
+                    continue;
+                };
+                let _ = write!(line, "  ★ {:>9}", format_elapsed(star.get_star_ts - unlock));
+                if is_new(part) {
+                    line.push_str(" (new)");
+                }
+            }
+
+            let _ = writeln!(out, "{}", line.trim_end());
+        }
+    }
+
+    out
+}
+
+fn with_delta(value: u64, delta: u64) -> String {
+    if delta == 0 {
+        value.to_string()
+    } else {
+        format!("{value} (+{delta})")
+    }
+}
+
+/// Formats the time between a puzzle unlocking and a star, e.g. `00:04:12` or `>24h`.
+fn format_elapsed(seconds: i64) -> String {
+    if seconds >= 24 * 3600 {
+        return ">24h".into();
+    }
+
+    let seconds = seconds.max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_elapsed, render, Leaderboard};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    // day 1 of 2023 unlocked at 1701406800.
+    const BEFORE: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "Alice", "stars": 2, "local_score": 4, "global_score": 0, "last_star_ts": 1701407580,
+                  "completion_day_level": {"1": {"1": {"get_star_ts": 1701407052, "star_index": 0}, "2": {"get_star_ts": 1701407580, "star_index": 1}}}},
+            "2": {"id": 2, "name": null, "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                  "completion_day_level": {}}
+        }
+    }"#;
+
+    const AFTER: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {"id": 1, "name": "Alice", "stars": 2, "local_score": 4, "global_score": 0, "last_star_ts": 1701407580,
+                  "completion_day_level": {"1": {"1": {"get_star_ts": 1701407052, "star_index": 0}, "2": {"get_star_ts": 1701407580, "star_index": 1}}}},
+            "2": {"id": 2, "name": null, "stars": 3, "local_score": 5, "global_score": 0, "last_star_ts": 1701500000,
+                  "completion_day_level": {"1": {"1": {"get_star_ts": 1701406900, "star_index": 2}, "2": {"get_star_ts": 1701500000, "star_index": 3}},
+                                           "2": {"1": {"get_star_ts": 1701493300, "star_index": 4}}}}
+        }
+    }"#;
+
+    #[test]
+    fn parses_api_json() {
+        let leaderboard = Leaderboard::from_json(AFTER).unwrap();
+        assert_eq!(leaderboard.members.len(), 2);
+        assert_eq!(
+            leaderboard.ranking()[0].display_name(),
+            "(anonymous user #2)"
+        );
+        assert_eq!(
+            leaderboard.members["1"].star(1, 2).unwrap().get_star_ts,
+            1_701_407_580
+        );
+        assert!(Leaderboard::from_json("{}").is_err());
+    }
+
+    #[test]
+    fn renders_deltas_since_previous_fetch() {
+        let before = Leaderboard::from_json(BEFORE).unwrap();
+        let after = Leaderboard::from_json(AFTER).unwrap();
+
+        assert_eq!(
+            render("1", &after, Some(&before)),
+            format!(
+                "{ANSI_BOLD}Leaderboard 1 (2023){ANSI_RESET}, 2 members
+
+   #  Score         Stars       Name
+   1  5 (+5)        3 (+3)      (anonymous user #2) ↑1
+   2  4             2           Alice ↓1
+
+{ANSI_BOLD}Day 2{ANSI_RESET}
+  (anonymous user #2)       ★  00:01:40 (new)
+
+{ANSI_BOLD}Day 1{ANSI_RESET}
+  Alice                     ★  00:04:12  ★  00:13:00
+  (anonymous user #2)       ★  00:01:40 (new)  ★      >24h (new)
+"
+            )
+        );
+    }
+
+    #[test]
+    fn formats_elapsed_time() {
+        assert_eq!(format_elapsed(252), "00:04:12");
+        assert_eq!(format_elapsed(3 * 3600 + 5), "03:00:05");
+        assert_eq!(format_elapsed(86400), ">24h");
+    }
+}
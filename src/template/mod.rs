@@ -1,48 +1,191 @@
 use crate::Day;
-use std::{env, fs};
+use error::{Context, Error};
+use input::InputOptions;
+use std::{fs, io};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod check_ref;
+pub mod cli;
 pub mod commands;
+pub mod completions;
+pub mod config;
+pub mod date;
+pub mod day_set;
+pub mod error;
+pub mod input;
+pub mod leaderboard;
+pub mod outcome;
 pub mod readme_benchmarks;
+pub mod readme_progress;
 pub mod runner;
+pub mod solution;
+pub mod trace;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 
 /// Helper function that reads a text file to a string.
+///
+/// # Panics
+/// Panics with the error of [`try_read_file`] if the file cannot be read.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file(folder, day).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+///
+/// # Panics
+/// Panics with the error of [`try_read_file_part`] if the file cannot be read.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    try_read_file_part(folder, day, part).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Reads a text file to a string with normalized line endings.
+/// The error names the missing file and suggests how to create it.
+pub fn try_read_file(folder: &str, day: Day) -> Result<String, Error> {
+    read_data_file(folder, day, &format!("{day}.txt"))
+}
+
+/// Reads a text file to a string with normalized line endings, appending a part suffix. E.g. like `01-2.txt`.
+/// The error names the missing file and suggests how to create it.
+pub fn try_read_file_part(folder: &str, day: Day, part: u8) -> Result<String, Error> {
+    read_data_file(folder, day, &format!("{day}-{part}.txt"))
+}
+
+fn read_data_file(folder: &str, day: Day, filename: &str) -> Result<String, Error> {
+    let path = config::get().paths.folder(folder).join(filename);
+
+    fs::read_to_string(&path)
+        .map(|content| input::normalize(&content, InputOptions::new()))
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Error::MissingFile {
+                path,
+                hint: file_hint(folder, day),
+            },
+            _ => Error::Context {
+                message: format!("failed to read \"{}\"", path.display()),
+                source: Box::new(e.into()),
+            },
+        })
+}
+
+/// Suggests how to fill a missing or empty data file.
+fn file_hint(folder: &str, day: Day) -> Option<String> {
+    match folder {
+        "inputs" => Some(format!("Run `cargo download {day}` to download it.")),
+        "examples" => Some("Copy the example from the puzzle description into it.".into()),
+        _ => None,
+    }
+}
+
+/// Reads the puzzle input for a day. Empty inputs (e.g. right after scaffolding) are treated as an error.
+/// If the input is missing or empty and `auto_download` is enabled in the config, the input is downloaded first,
+/// provided that an aoc-cli session is configured.
+/// The input is normalized according to `options`, in strict mode unexpected characters are reported as warnings.
+pub fn read_input(day: Day, options: InputOptions) -> Result<String, Error> {
+    let input = match read_non_empty_input(day, options) {
+        Err(Error::MissingFile { .. } | Error::EmptyFile { .. })
+            if config::get().auto_download && aoc_cli::has_session() =>
+        {
+            println!("Input for day {day} is missing, downloading it...");
+            aoc_cli::check()?;
+            aoc_cli::download(day).context("failed to download input")?;
+            read_non_empty_input(day, options)
+        }
+        result => result,
+    }?;
+
+    if options.strict {
+        let path = config::get().paths.input(day);
+        for warning in input::check(&input) {
+            eprintln!("Warning: {}:{warning}", path.display());
+        }
+    }
+
+    Ok(input)
+}
+
+fn read_non_empty_input(day: Day, options: InputOptions) -> Result<String, Error> {
+    let input = input::normalize(&try_read_file("inputs", day)?, options);
+
+    if input.is_empty() {
+        return Err(Error::EmptyFile {
+            path: config::get().paths.input(day),
+            hint: file_hint("inputs", day),
+        });
+    }
+
+    Ok(input)
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Input options (see [`InputOptions`]) can be set after the day, e.g. `solution!(2, trim = true)`.
+/// Extras of the [`Solution`](solution::Solution) are set the same way, e.g. `solution!(7, generator = generate)`
+/// or `solution!(6, naive_one = naive::part_one, variants_two = [part_two::quadratic])`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    // Sorts the options into input options and extras of the solution.
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; generator = $value:expr, $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)*]; [$($extras)* .generator($value)]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; visualizer = $value:expr, $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)*]; [$($extras)* .visualizer($value)]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; variants_one = [$($variant:path),* $(,)?], $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)*]; [$($extras)* $(.variant_one(stringify!($variant), $variant))*]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; variants_two = [$($variant:path),* $(,)?], $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)*]; [$($extras)* $(.variant_two(stringify!($variant), $variant))*]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; naive_one = $value:expr, $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)*]; [$($extras)* .naive_one($value)]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; naive_two = $value:expr, $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)*]; [$($extras)* .naive_two($value)]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; $option:ident = $value:expr, $($rest:tt)*) => {
+        $crate::solution!(@options $day; [$($input)* .$option($value)]; [$($extras)*]; $($rest)*);
+    };
+    (@options $day:expr; [$($input:tt)*]; [$($extras:tt)*]; $(,)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
-        fn main() {
+        /// How the input of the current day is normalized.
+        const INPUT_OPTIONS: advent_of_code::template::input::InputOptions =
+            advent_of_code::template::input::InputOptions::new()$($input)*;
+
+        fn main() -> std::process::ExitCode {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let solution =
+                advent_of_code::template::solution::Solution::new(part_one, part_two)$($extras)*;
+            if let Some(outcome) = solution.run_mode(DAY, INPUT_OPTIONS) {
+                return outcome.into();
+            }
+
+            let _trace = advent_of_code::template::trace::init();
+            let input = match advent_of_code::template::read_input(DAY, INPUT_OPTIONS) {
+                Ok(input) => input,
+                Err(e) => {
+                    advent_of_code::template::error::report(&e);
+                    return advent_of_code::template::outcome::Outcome::Error.into();
+                }
+            };
+            let part_one = run_part(solution.part_one, &input, DAY, 1);
+            let part_two = run_part(solution.part_two, &input, DAY, 2);
+            part_one.max(part_two).into()
         }
     };
+    // The options are passed on as tokens, as lists of variants can't be matched once they are parsed as an `expr`.
+    ($day:expr $(,)?) => {
+        $crate::solution!(@options $day; []; [];);
+    };
+    ($day:expr, $($options:tt)+) => {
+        $crate::solution!(@options $day; []; []; $($options)+,);
+    };
 }
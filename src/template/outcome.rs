@@ -0,0 +1,116 @@
+/// Outcomes of running solutions and the exit codes they map to.
+/// Solution binaries exit with the code of their worst part, `solve` and `all` forward the worst code of the days they ran.
+use std::fmt::Display;
+use std::process::ExitCode;
+
+/// The outcome of running one or more solution parts, ordered from best to worst.
+///
+/// | Outcome       | Exit code |
+/// | ------------- | --------- |
+/// | `Success`     | 0         |
+/// | `Error`       | 1         |
+/// | `BuildFailed` | 2         |
+/// | `Panicked`    | 3         |
+/// | `Unsolved`    | 4         |
+/// | `Regression`  | 5         |
+/// | `Timeout`     | 6         |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    /// Every part produced an answer that matches the known answer, if there is one.
+    Success,
+    /// A part returned `None`.
+    Unsolved,
+    /// A part produced an answer that differs from the known answer.
+    Regression,
+    /// A part panicked.
+    Panicked,
+    /// The solution did not finish within the configured timeout.
+    Timeout,
+    /// The solution binary could not be built.
+    BuildFailed,
+    /// Any other error, e.g. invalid arguments or a failing aoc-cli call.
+    Error,
+}
+
+impl Outcome {
+    /// Returns the exit code of the outcome.
+    #[must_use]
+    pub fn code(self) -> u8 {
+        match self {
+            Outcome::Success => 0,
+            Outcome::Error => 1,
+            Outcome::BuildFailed => 2,
+            Outcome::Panicked => 3,
+            Outcome::Unsolved => 4,
+            Outcome::Regression => 5,
+            Outcome::Timeout => 6,
+        }
+    }
+
+    /// Maps the exit code of a solution binary back to its outcome.
+    /// Unknown codes (e.g. from a panic outside of a part) map to [`Outcome::Error`].
+    #[must_use]
+    pub fn from_code(code: i32) -> Self {
+        match code {
+            0 => Outcome::Success,
+            2 => Outcome::BuildFailed,
+            3 => Outcome::Panicked,
+            4 => Outcome::Unsolved,
+            5 => Outcome::Regression,
+            6 => Outcome::Timeout,
+            _ => Outcome::Error,
+        }
+    }
+
+    #[must_use]
+    pub fn is_success(self) -> bool {
+        self == Outcome::Success
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Success => write!(f, "solved"),
+            Outcome::Unsolved => write!(f, "a part returned no answer"),
+            Outcome::Regression => write!(f, "an answer differs from the known answer"),
+            Outcome::Panicked => write!(f, "a part panicked"),
+            Outcome::Timeout => write!(f, "timed out"),
+            Outcome::BuildFailed => write!(f, "failed to build"),
+            Outcome::Error => write!(f, "exited with an error"),
+        }
+    }
+}
+
+impl From<Outcome> for ExitCode {
+    fn from(outcome: Outcome) -> Self {
+        ExitCode::from(outcome.code())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Outcome;
+
+    #[test]
+    fn codes_round_trip() {
+        for outcome in [
+            Outcome::Success,
+            Outcome::Error,
+            Outcome::BuildFailed,
+            Outcome::Panicked,
+            Outcome::Unsolved,
+            Outcome::Regression,
+            Outcome::Timeout,
+        ] {
+            assert_eq!(Outcome::from_code(outcome.code().into()), outcome);
+        }
+    }
+
+    #[test]
+    fn worst_outcome_wins() {
+        let outcomes = [Outcome::Unsolved, Outcome::Panicked, Outcome::Success];
+        assert_eq!(outcomes.into_iter().max(), Some(Outcome::Panicked));
+        assert_eq!(Outcome::from_code(101), Outcome::Error);
+    }
+}
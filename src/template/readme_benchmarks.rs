@@ -1,23 +1,14 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::fs;
+use std::path::Path;
 
+use crate::template::config;
+use crate::template::error::{Context, Error};
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
-#[derive(Debug)]
-pub enum Error {
-    Parser(String),
-    IO(io::Error),
-}
-
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
-}
-
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
@@ -27,33 +18,47 @@ pub struct Timings {
 }
 
 pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+    pub(crate) pos_start: usize,
+    pub(crate) pos_end: usize,
 }
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    Path::new(".")
+        .join(config::get().paths.bin(day))
+        .display()
+        .to_string()
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Finds the table between two occurrences of `marker`, including the markers.
+/// Only markers on lines of their own count, so the marker can be quoted in the rest of the readme.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let mut offset = 0;
+    let mut matches = vec![];
+
+    for line in readme.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if !trimmed.is_empty() && trimmed.split(marker).all(str::is_empty) {
+            matches.extend(line.match_indices(marker).map(|(pos, _)| offset + pos));
+        }
+        offset += line.len();
+    }
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+        return Err(Error::Readme(
+            "too many occurences of marker in README.".into(),
         ));
     }
 
     let pos_start = matches
         .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+        .copied()
+        .ok_or_else(|| Error::Readme("Could not find table start position.".into()))?;
 
     let pos_end = matches
         .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        .map(|pos| pos + marker.len())
+        .ok_or_else(|| Error::Readme("Could not find table end position.".into()))?;
 
     Ok(TablePosition { pos_start, pos_end })
 }
@@ -87,24 +92,79 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+/// Replaces the table, returns a diff of the previous and the new table.
+fn update_content(
+    s: &mut String,
+    timings: Vec<Timings>,
+    total_millis: f64,
+) -> Result<String, Error> {
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
+    let diff = diff_lines(&s[positions.pos_start..positions.pos_end], &table);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+    Ok(diff)
 }
 
+/// Writes the benchmarks to the readme, printing how the table changed.
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+    update_readme(|readme| {
+        let diff = update_content(readme, timings, total_millis)?;
+        println!("\n{diff}");
+        Ok(())
+    })
+}
+
+/// Renders a line based diff, prefixing removed lines with `-`, added lines with `+` and unchanged lines with a space.
+pub(crate) fn diff_lines(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // common[i][j] is the length of the longest common subsequence of `old[i..]` and `new[j..]`.
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            lines.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            lines.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Reads the readme, applies `update` and writes it back.
+pub(crate) fn update_readme(
+    update: impl FnOnce(&mut String) -> Result<(), Error>,
+) -> Result<(), Error> {
     let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    let mut readme =
+        String::from_utf8_lossy(&fs::read(path).context("failed to read README.md")?).to_string();
+    update(&mut readme)?;
+    fs::write(path, &readme).context("failed to write README.md")?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{diff_lines, update_content, Timings, MARKER};
     use crate::day;
 
     fn get_mock_timings() -> Vec<Timings> {
@@ -160,6 +220,36 @@ mod tests {
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
+    #[test]
+    fn diffs_tables() {
+        assert_eq!(
+            diff_lines("a\nb\nc\nd", "a\nc\nx\nd\ne"),
+            ["  a", "- b", "  c", "+ x", "  d", "+ e"].join("\n")
+        );
+        assert_eq!(diff_lines("a\nb", "a\nb"), "  a\n  b");
+    }
+
+    #[test]
+    fn returns_diff_of_table() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+
+        let mut timings = get_mock_timings();
+        timings[1].part_2 = Some("35ms".into());
+        let diff = update_content(&mut s, timings, 185.0).unwrap();
+        let changes: Vec<&str> = diff.lines().filter(|line| !line.starts_with(' ')).collect();
+
+        assert_eq!(
+            changes,
+            vec![
+                "- | [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+                "+ | [Day 2](./src/bin/02.rs) | `30ms` | `35ms` |",
+                "- **Total: 190.00ms**",
+                "+ **Total: 185.00ms**",
+            ]
+        );
+    }
+
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
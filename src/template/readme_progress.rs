@@ -0,0 +1,186 @@
+/// Module that updates the readme with a progress table, built from known answers and submitted stars.
+/// Unlike the `readme-stars` workflow, this works offline and needs no session cookie.
+use std::fs;
+use std::path::Path;
+
+use crate::template::readme_benchmarks::{get_path_for_bin, locate_table, update_readme};
+use crate::template::{answers, config, error::Error};
+use crate::{all_days, Day};
+
+static MARKER: &str = "<!--- progress table --->";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub day: Day,
+    /// The puzzle title, read from the downloaded puzzle description.
+    pub title: Option<String>,
+    /// Solved parts, see [`answers::Answers::progress`].
+    pub stars: u8,
+    /// Path of the downloaded puzzle description.
+    pub puzzle: Option<String>,
+}
+
+/// Collects the progress of every day that has a solution or stars.
+#[must_use]
+pub fn collect() -> Vec<Progress> {
+    let paths = &config::get().paths;
+
+    all_days()
+        .filter_map(|day| {
+            let stars = answers::read(day).progress();
+            let puzzle_path = paths.puzzle(day);
+            let puzzle = fs::read_to_string(&puzzle_path).ok();
+
+            if stars == 0 && !paths.bin(day).exists() {
+                return None;
+            }
+
+            Some(Progress {
+                day,
+                title: puzzle.as_deref().and_then(parse_title),
+                stars,
+                puzzle: puzzle.map(|_| link(&puzzle_path)),
+            })
+        })
+        .collect()
+}
+
+/// Links to `path` from the readme, relative paths get a `./` prefix and absolute ones are kept as they are.
+fn link(path: &Path) -> String {
+    Path::new(".").join(path).display().to_string()
+}
+
+/// Reads the title from a puzzle description, which starts with a heading like `## --- Day 1: Trebuchet?! ---`.
+fn parse_title(puzzle: &str) -> Option<String> {
+    puzzle.lines().find_map(|line| {
+        let (_, rest) = line.split_once("--- Day ")?;
+        let (_, title) = rest.split_once(": ")?;
+        Some(title.trim_end_matches('-').trim().to_string())
+    })
+}
+
+fn construct_table(prefix: &str, progress: &[Progress]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("{prefix} Progress"),
+        String::new(),
+        "| Day | Title | Stars | Puzzle |".into(),
+        "| :---: | :--- | :---: | :---: |".into(),
+    ];
+
+    for day in progress {
+        lines.push(format!(
+            "| [Day {}]({}) | {} | {} | {} |",
+            day.day.into_inner(),
+            get_path_for_bin(day.day),
+            day.title.as_deref().unwrap_or("-").replace('|', "\\|"),
+            if day.stars == 0 {
+                "-".into()
+            } else {
+                "⭐".repeat(day.stars.into())
+            },
+            day.puzzle
+                .as_ref()
+                .map_or_else(|| "-".into(), |path| format!("[📄]({path})")),
+        ));
+    }
+
+    let stars: u32 = progress.iter().map(|day| u32::from(day.stars)).sum();
+    lines.push(String::new());
+    lines.push(format!("**Total: {stars} ⭐**"));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &[Progress]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+pub fn update(progress: &[Progress]) -> Result<(), Error> {
+    update_readme(|readme| update_content(readme, progress))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{link, parse_title, update_content, Progress, MARKER};
+    use crate::day;
+
+    fn get_mock_progress() -> Vec<Progress> {
+        vec![
+            Progress {
+                day: day!(1),
+                title: Some("Trebuchet?!".into()),
+                stars: 2,
+                puzzle: Some("./data/puzzles/01.md".into()),
+            },
+            Progress {
+                day: day!(3),
+                title: None,
+                stars: 0,
+                puzzle: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn links_relative_and_absolute_paths() {
+        assert_eq!(
+            link(Path::new("data/puzzles/01.md")),
+            "./data/puzzles/01.md"
+        );
+        assert_eq!(link(Path::new("/aoc/puzzles/01.md")), "/aoc/puzzles/01.md");
+    }
+
+    #[test]
+    fn parses_puzzle_title() {
+        assert_eq!(
+            parse_title("\\## --- Day 1: Trebuchet?! ---\nSomething is wrong"),
+            Some("Trebuchet?!".into())
+        );
+        assert_eq!(parse_title("no title"), None);
+    }
+
+    #[test]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        assert!(update_content(&mut s, &get_mock_progress()).is_err());
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbaz");
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        update_content(&mut s, &get_mock_progress()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- progress table --->",
+            "## Progress",
+            "",
+            "| Day | Title | Stars | Puzzle |",
+            "| :---: | :--- | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | Trebuchet?! | ⭐⭐ | [📄](./data/puzzles/01.md) |",
+            "| [Day 3](./src/bin/03.rs) | - | - | - |",
+            "",
+            "**Total: 2 ⭐**",
+            "<!--- progress table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn ignores_marker_quoted_in_prose() {
+        let mut s = format!("{MARKER}\nfoo\nThe table goes between the `{MARKER}` markers.\n");
+        update_content(&mut s, &get_mock_progress()).unwrap();
+
+        assert!(s.starts_with("<!--- progress table --->\n## Progress\n"));
+        assert!(s.ends_with("<!--- progress table --->\nfoo\nThe table goes between the `<!--- progress table --->` markers.\n"));
+    }
+}
@@ -1,43 +1,367 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::error::{self, Context, Error};
+use crate::template::solution::Variant;
+use crate::template::{answers, aoc_cli, config, outcome::Outcome, trace, ANSI_ITALIC, ANSI_RESET};
+use crate::{ocr, Day};
+use std::cell::{Cell, RefCell};
 use std::fmt::Display;
+use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Runs, prints and optionally submits a solution part. Returns how the part went, see [`Outcome`].
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) -> Outcome {
     let part_str = format!("Part {part}");
+    let span = tracing::info_span!(trace::PART_SPAN, { trace::PART_FIELD } = part);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let timed = catch_panic(|| {
+        run_timed(&func, input, &span, |result| {
+            print_result(result, &part_str, "");
+        })
+    });
+
+    let (result, duration, samples) = match timed {
+        Ok(timed) => timed,
+        Err(panic) => {
+            print_panic(&panic, &part_str);
+            return Outcome::Panicked;
+        }
+    };
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let Some(result) = result else {
+        return Outcome::Unsolved;
+    };
+
+    let outcome = match answers::read(day).get(part) {
+        Some(expected) if answer_string(&result).as_deref() != Some(expected) => {
+            println!("{part_str}: ✖ expected {expected}");
+            Outcome::Regression
+        }
+        _ => Outcome::Success,
+    };
+
+    if let Err(e) = submit_result(result, day, part) {
+        error::report(&e);
+        return outcome.max(Outcome::Error);
+    }
+
+    outcome
+}
+
+/// Calls a part repeatedly for `duration` and prints its answer and iteration stats.
+/// Meant for external profilers, so the process spends nearly all of its time in the part.
+pub fn loop_part<T: Display>(
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+    part: u8,
+    duration: Duration,
+) -> Outcome {
+    let part_str = format!("Part {part}");
+    println!(
+        "{ANSI_ITALIC}Looping part {part} for {duration:.1?} in process {}.{ANSI_RESET}",
+        process::id()
+    );
+
+    let looped = catch_panic(|| {
+        // the first call is not timed, it may warm up caches and allocations.
+        let result = func(black_box(input));
+        let mut timers = LoopTimers::default();
+        let start = Instant::now();
+
+        while start.elapsed() < duration {
+            let timer = Instant::now();
+            black_box(func(black_box(input)));
+            timers.push(timer.elapsed());
+        }
+
+        (result, timers)
+    });
+
+    let (result, timers) = match looped {
+        Ok(looped) => looped,
+        Err(panic) => {
+            print_panic(&panic, &part_str);
+            return Outcome::Panicked;
+        }
+    };
+
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&timers.mean(), timers.iterations),
+    );
+    println!(
+        "{part_str}: looped {} times, min {:.1?}, max {:.1?}",
+        timers.iterations, timers.min, timers.max
+    );
+
+    match result {
+        Some(_) => Outcome::Success,
+        None => Outcome::Unsolved,
+    }
+}
+
+/// The benchmark of one variant of a part, see [`compare_variants`].
+struct VariantRun {
+    name: String,
+    answer: String,
+    /// The mean duration and the number of samples, [`None`] if the variant panicked.
+    timing: Option<(Duration, u128)>,
+    /// Whether the variant returned the same answer as the first variant that returned one.
+    agrees: bool,
+}
+
+/// Benchmarks every variant of a part on the same input and prints a table that compares them with the first variant
+/// that returned an answer. Returns [`Outcome::Regression`] if a variant disagrees with it.
+pub fn compare_variants<T: Display + PartialEq>(
+    part: u8,
+    variants: &[Variant<T>],
+    input: &str,
+) -> Outcome {
+    let mut stdout = stdout();
+    let mut expected = None;
+    let mut runs = vec![];
+    let mut outcome = Outcome::Success;
+
+    for (name, func) in variants {
+        print!("\r\x1b[K{ANSI_ITALIC}Part {part}: benching {name}{ANSI_RESET}");
+        let _ = stdout.flush();
+
+        let timed = catch_panic(|| {
+            let timer = Instant::now();
+            let result = func(input);
+            let base_time = timer.elapsed();
+            (result, sample(func, input, &base_time))
+        });
+
+        runs.push(match timed {
+            Ok((result, timing)) => {
+                let answer = result.as_ref().map_or("None".into(), ToString::to_string);
+                let agrees = match (result, &expected) {
+                    (None, _) => {
+                        outcome = outcome.max(Outcome::Unsolved);
+                        false
+                    }
+                    (Some(result), None) => {
+                        expected = Some(result);
+                        true
+                    }
+                    (Some(result), Some(expected)) => {
+                        if result != *expected {
+                            outcome = outcome.max(Outcome::Regression);
+                        }
+                        result == *expected
+                    }
+                };
+                VariantRun {
+                    name: (*name).to_string(),
+                    answer,
+                    timing: Some(timing),
+                    agrees,
+                }
+            }
+            Err(panic) => {
+                outcome = outcome.max(Outcome::Panicked);
+                VariantRun {
+                    name: (*name).to_string(),
+                    answer: panic.to_string(),
+                    timing: None,
+                    agrees: false,
+                }
+            }
+        });
     }
+
+    print!("\r\x1b[K");
+    print!("{}", render_variants(part, &runs));
+    outcome
+}
+
+/// Renders the runs as a table. The speed-up is relative to the first variant that returned an answer,
+/// it is `n/a` if none did.
+fn render_variants(part: u8, runs: &[VariantRun]) -> String {
+    let baseline = runs
+        .iter()
+        .find(|run| run.agrees)
+        .and_then(|run| run.timing)
+        .map(|timing| timing.0);
+
+    let rows: Vec<[String; 4]> = runs
+        .iter()
+        .map(|run| {
+            let answer = if run.agrees {
+                run.answer.clone()
+            } else {
+                format!("✖ {}", run.answer)
+            };
+            let (time, speed_up) = match run.timing {
+                Some((time, samples)) => (
+                    format!("{time:.1?} @ {samples} samples"),
+                    baseline.map_or("n/a".into(), |baseline| {
+                        format!(
+                            "{:.2}x",
+                            baseline.as_secs_f64() / time.as_secs_f64().max(1e-12)
+                        )
+                    }),
+                ),
+                None => ("-".into(), "-".into()),
+            };
+            [run.name.clone(), answer, time, speed_up]
+        })
+        .collect();
+
+    let header = [
+        format!("Part {part}"),
+        "Answer".into(),
+        "Time".into(),
+        "Speed-up".into(),
+    ];
+    let widths: Vec<usize> = (0..4)
+        .map(|column| {
+            rows.iter()
+                .chain([&header])
+                .map(|row| row[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut table = String::new();
+    for (index, row) in [&header].into_iter().chain(&rows).enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        if index == 0 {
+            table.push_str(&format!("{ANSI_BOLD}{}{ANSI_RESET}\n", line.trim_end()));
+        } else {
+            table.push_str(&format!("{}\n", line.trim_end()));
+        }
+    }
+    table
+}
+
+/// Running stats of [`loop_part`], which can run too many iterations to keep every duration.
+#[derive(Default)]
+struct LoopTimers {
+    iterations: u128,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+}
+
+impl LoopTimers {
+    fn push(&mut self, elapsed: Duration) {
+        self.min = if self.iterations == 0 {
+            elapsed
+        } else {
+            self.min.min(elapsed)
+        };
+        self.max = self.max.max(elapsed);
+        self.total += elapsed;
+        self.iterations += 1;
+    }
+
+    fn mean(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation)]
+        Duration::from_nanos((self.total.as_nanos() / self.iterations.max(1)) as u64)
+    }
+}
+
+/// A panic that was caught while running a solution part.
+pub(crate) struct CaughtPanic {
+    message: String,
+    location: Option<String>,
+}
+
+impl Display for CaughtPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "panicked at {location}: {}", self.message),
+            None => write!(f, "panicked: {}", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    static CAUGHT_PANIC: RefCell<Option<CaughtPanic>> = const { RefCell::new(None) };
+}
+
+static INSTALL_PANIC_HOOK: Once = Once::new();
+
+/// Runs `func`, converting a panic into an error instead of aborting the process.
+/// While `func` runs, the default panic output is suppressed so that the panic can be printed as a failed part.
+pub(crate) fn catch_panic<R>(func: impl FnOnce() -> R) -> Result<R, CaughtPanic> {
+    INSTALL_PANIC_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !IS_CATCHING.get() {
+                default_hook(info);
+                return;
+            }
+
+            let payload = info.payload();
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| (*s).to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "Box<dyn Any>".into());
+
+            CAUGHT_PANIC.set(Some(CaughtPanic {
+                message,
+                location: info.location().map(ToString::to_string),
+            }));
+        }));
+    });
+
+    IS_CATCHING.set(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.set(false);
+
+    result.map_err(|_| {
+        CAUGHT_PANIC.take().unwrap_or_else(|| CaughtPanic {
+            message: "unknown panic".into(),
+            location: None,
+        })
+    })
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is benched (approx. `bench.target_time` of execution time, bounded by `bench.min_samples` and `bench.max_samples`.)
+///
+/// Only the first execution runs inside `span`, benchmark samples are not traced.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    span: &tracing::Span,
     hook: impl Fn(&T),
 ) -> (T, Duration, u128) {
     let timer = Instant::now();
-    let result = func(input.clone());
+    let result = span.in_scope(|| func(input.clone()));
     let base_time = timer.elapsed();
 
     hook(&result);
 
     let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+        tracing::subscriber::with_default(tracing::subscriber::NoSubscriber::default(), || {
+            bench(func, input, &base_time)
+        })
     } else {
         (base_time, 1)
     };
@@ -51,13 +375,17 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    sample(func, input, base_time)
+}
+
+/// Runs `func` for about `bench.target_time`, returns the mean duration and the number of samples.
+fn sample<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+    let settings = &config::get().bench;
+    let bench_iterations = (settings.target_time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(
+            settings.min_samples.into(),
+            settings.max_samples.max(settings.min_samples).into(),
+        );
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -105,6 +433,9 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     print!("\r");
                     println!("{str}");
                     println!("{result}");
+                    if let Some(letters) = ocr::parse(&result.to_string()) {
+                        println!("▲ {ANSI_BOLD}{letters}{ANSI_RESET}");
+                    }
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
@@ -127,41 +458,95 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
+fn print_panic(panic: &CaughtPanic, part: &str) {
+    print!("\r");
+    println!("{part}: ✖ {panic}");
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
 ///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+fn submit_result<T: Display>(result: T, day: Day, part: u8) -> Result<(), Error> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    let Some(part_index) = args.iter().position(|x| x == "--submit") else {
+        return Ok(());
+    };
+
+    let part_submit = args
+        .get(part_index + 1)
+        .and_then(|x| x.parse::<u8>().ok())
+        .ok_or_else(|| {
+            Error::InvalidArguments(
+                "Unexpected command-line input. Format: cargo solve 1 --submit 1".into(),
+            )
+        })?;
+
+    if part_submit != part {
+        return Ok(());
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    aoc_cli::check()?;
+
+    let answer = answer_string(&result).ok_or_else(|| {
+        Error::InvalidArguments("could not decode the multi-line answer into letters.".into())
+    })?;
+
+    println!("Submitting result via aoc-cli...");
+    if aoc_cli::submit(day, part, &answer).context("failed to submit answer")? {
+        answers::record_star(day, part, &answer).context("failed to record the star")?;
     }
+    Ok(())
+}
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+/// Converts a result into the string that is submitted as the answer.
+/// Multi-line results are treated as ASCII-art letters and decoded, returns [`None`] if that fails.
+fn answer_string<T: Display>(result: &T) -> Option<String> {
+    let answer = result.to_string();
+    if answer.contains('\n') {
+        ocr::parse(&answer)
+    } else {
+        Some(answer)
+    }
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    };
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render_variants, VariantRun};
+    use std::time::Duration;
 
-    if part_submit != part {
-        return None;
+    fn run(name: &str, answer: &str, millis: Option<u64>, agrees: bool) -> VariantRun {
+        VariantRun {
+            name: name.into(),
+            answer: answer.into(),
+            timing: millis.map(|millis| (Duration::from_millis(millis), 10)),
+            agrees,
+        }
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn compares_with_first_answer() {
+        let table = render_variants(
+            1,
+            &[
+                run("part_one", "boom", None, false),
+                run("part_one::none", "None", Some(1), false),
+                run("part_one::fast", "42", Some(2), true),
+                run("part_one::slow", "42", Some(4), true),
+            ],
+        );
+        let lines: Vec<&str> = table.lines().skip(1).collect();
+
+        assert!(lines[0].contains("✖ boom"), "{table}");
+        assert!(lines[0].ends_with(" -"), "{table}");
+        assert!(lines[1].ends_with("2.00x"), "{table}");
+        assert!(lines[2].ends_with("1.00x"), "{table}");
+        assert!(lines[3].ends_with("0.50x"), "{table}");
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn marks_speed_up_without_answers() {
+        let table = render_variants(2, &[run("part_two", "None", Some(1), false)]);
+        assert!(table.lines().nth(1).unwrap().ends_with("n/a"), "{table}");
+    }
 }
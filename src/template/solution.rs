@@ -0,0 +1,308 @@
+/// The parts of a solution, together with the extras that are registered in [`solution!`](crate::solution).
+/// The solution binary runs the parts by default, command-line flags select one of the other modes.
+use std::env;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::rng::Rng;
+use crate::template::check_ref::{self, CheckInput};
+use crate::template::error::{self, Context, Error};
+use crate::template::input::InputOptions;
+use crate::template::outcome::Outcome;
+use crate::template::{config, read_input, runner, try_read_file, try_read_file_part};
+use crate::Day;
+
+/// Generates a random input of roughly `size`, e.g. the number of lines or the width of a grid.
+pub type Generator = fn(&mut Rng, usize) -> String;
+
+/// A named implementation of a part, e.g. `part_two::vec`.
+pub type Variant<T> = (&'static str, fn(&str) -> Option<T>);
+
+/// Writes visualizations of the input to a directory, see [`crate::visualize`].
+pub type Visualizer = fn(&str, &Path) -> io::Result<()>;
+
+/// The sizes and seeds of the generated inputs that `cargo check-ref` runs on.
+const CHECK_SIZES: std::ops::RangeInclusive<usize> = 1..=5;
+const CHECK_SEEDS: std::ops::Range<u64> = 0..4;
+
+pub struct Solution<T1, T2> {
+    pub part_one: fn(&str) -> Option<T1>,
+    pub part_two: fn(&str) -> Option<T2>,
+    pub generator: Option<Generator>,
+    pub visualizer: Option<Visualizer>,
+    /// A simple but slow implementation of part one, to check `part_one` against.
+    pub naive_one: Option<fn(&str) -> Option<T1>>,
+    /// A simple but slow implementation of part two, to check `part_two` against.
+    pub naive_two: Option<fn(&str) -> Option<T2>>,
+    /// Other implementations of part one with their names, e.g. `part_one::vec`, benchmarked by `--variants`.
+    pub variants_one: Vec<Variant<T1>>,
+    /// Other implementations of part two with their names, benchmarked by `--variants`.
+    pub variants_two: Vec<Variant<T2>>,
+}
+
+impl<T1: Display + PartialEq, T2: Display + PartialEq> Solution<T1, T2> {
+    #[must_use]
+    pub fn new(part_one: fn(&str) -> Option<T1>, part_two: fn(&str) -> Option<T2>) -> Self {
+        Self {
+            part_one,
+            part_two,
+            generator: None,
+            visualizer: None,
+            naive_one: None,
+            naive_two: None,
+            variants_one: vec![],
+            variants_two: vec![],
+        }
+    }
+
+    /// Registers an input generator, used by `cargo gen`.
+    #[must_use]
+    pub fn generator(mut self, generator: Generator) -> Self {
+        self.generator = Some(generator);
+        self
+    }
+
+    /// Registers a visualizer, used by `cargo solve DD --visualize <dir>`.
+    #[must_use]
+    pub fn visualizer(mut self, visualizer: Visualizer) -> Self {
+        self.visualizer = Some(visualizer);
+        self
+    }
+
+    /// Registers a naive implementation of part one, used by `cargo check-ref`.
+    #[must_use]
+    pub fn naive_one(mut self, naive: fn(&str) -> Option<T1>) -> Self {
+        self.naive_one = Some(naive);
+        self
+    }
+
+    /// Registers a naive implementation of part two, used by `cargo check-ref`.
+    #[must_use]
+    pub fn naive_two(mut self, naive: fn(&str) -> Option<T2>) -> Self {
+        self.naive_two = Some(naive);
+        self
+    }
+
+    /// Registers another implementation of part one, used by `cargo solve DD --variants`.
+    #[must_use]
+    pub fn variant_one(mut self, name: &'static str, variant: fn(&str) -> Option<T1>) -> Self {
+        self.variants_one.push((name, variant));
+        self
+    }
+
+    /// Registers another implementation of part two, used by `cargo solve DD --variants`.
+    #[must_use]
+    pub fn variant_two(mut self, name: &'static str, variant: fn(&str) -> Option<T2>) -> Self {
+        self.variants_two.push((name, variant));
+        self
+    }
+
+    /// Runs the mode selected by the command-line flags, [`None`] if the parts should run as usual.
+    #[must_use]
+    pub fn run_mode(&self, day: Day, options: InputOptions) -> Option<Outcome> {
+        let args: Vec<String> = env::args().collect();
+
+        if args.iter().any(|arg| arg == "--generate") {
+            return Some(report(self.generate(&args).map(|()| Outcome::Success)));
+        }
+
+        if args.iter().any(|arg| arg == "--check-ref") {
+            return Some(self.check_ref(day, options));
+        }
+
+        if args.iter().any(|arg| arg == "--loop-part") {
+            return Some(report(self.loop_part(day, options, &args)));
+        }
+
+        if args.iter().any(|arg| arg == "--variants") {
+            return Some(report(self.compare_variants(day, options)));
+        }
+
+        if args.iter().any(|arg| arg == "--visualize") {
+            return Some(report(
+                self.visualize(day, options, &args)
+                    .map(|()| Outcome::Success),
+            ));
+        }
+
+        None
+    }
+
+    /// Prints a generated input to stdout, called with `--generate --size <n> --seed <seed>`.
+    fn generate(&self, args: &[String]) -> Result<(), Error> {
+        let generator = self.generator.ok_or_else(|| {
+            Error::InvalidArguments(
+                "this day has no generator, register one with `solution!(DD, generator = ...)`."
+                    .into(),
+            )
+        })?;
+
+        let size = parse_value(args, "--size")?.ok_or_else(|| {
+            Error::InvalidArguments("missing `--size <n>` for the generator.".into())
+        })?;
+        let seed = parse_value(args, "--seed")?.unwrap_or_default();
+
+        let input = generator(&mut Rng::new(seed), size);
+        io::stdout().write_all(input.as_bytes())?;
+        Ok(())
+    }
+
+    /// Benchmarks the parts against their variants on the input, called with `--variants`.
+    fn compare_variants(&self, day: Day, options: InputOptions) -> Result<Outcome, Error> {
+        if self.variants_one.is_empty() && self.variants_two.is_empty() {
+            return Err(Error::InvalidArguments(
+                "this day has no variants, register them with `solution!(DD, variants_two = [part_two::vec])`.".into(),
+            ));
+        }
+
+        let input = read_input(day, options)?;
+        let mut outcome = Outcome::Success;
+
+        if !self.variants_one.is_empty() {
+            let variants: Vec<_> = [("part_one", self.part_one)]
+                .into_iter()
+                .chain(self.variants_one.iter().copied())
+                .collect();
+            outcome = outcome.max(runner::compare_variants(1, &variants, &input));
+        }
+
+        if !self.variants_two.is_empty() {
+            if !self.variants_one.is_empty() {
+                println!();
+            }
+            let variants: Vec<_> = [("part_two", self.part_two)]
+                .into_iter()
+                .chain(self.variants_two.iter().copied())
+                .collect();
+            outcome = outcome.max(runner::compare_variants(2, &variants, &input));
+        }
+
+        Ok(outcome)
+    }
+
+    /// Writes visualizations of the input, called with `--visualize <dir>`.
+    fn visualize(&self, day: Day, options: InputOptions, args: &[String]) -> Result<(), Error> {
+        let visualizer = self.visualizer.ok_or_else(|| {
+            Error::InvalidArguments(
+                "this day has no visualizer, register one with `solution!(DD, visualizer = ...)`."
+                    .into(),
+            )
+        })?;
+        let dir: PathBuf = parse_value(args, "--visualize")?.ok_or_else(|| {
+            Error::InvalidArguments("missing `--visualize <dir>` for the visualizer.".into())
+        })?;
+
+        let input = read_input(day, options)?;
+        fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create \"{}\"", dir.display()))?;
+        visualizer(&input, &dir).with_context(|| format!("failed to visualize day {day}"))
+    }
+
+    /// Reads the input once and runs one part in a loop, called with `--loop-part <part> --duration <duration>`.
+    fn loop_part(
+        &self,
+        day: Day,
+        options: InputOptions,
+        args: &[String],
+    ) -> Result<Outcome, Error> {
+        let duration = match parse_value::<String>(args, "--duration")? {
+            Some(duration) => config::parse_duration(&duration).map_err(Error::InvalidArguments)?,
+            None => Duration::from_secs(10),
+        };
+        let input = read_input(day, options)?;
+
+        match parse_value(args, "--loop-part")? {
+            Some(1) => Ok(runner::loop_part(self.part_one, &input, 1, duration)),
+            Some(2) => Ok(runner::loop_part(self.part_two, &input, 2, duration)),
+            _ => Err(Error::InvalidArguments(
+                "`--loop-part` expects the part, 1 or 2.".into(),
+            )),
+        }
+    }
+
+    /// Checks every part that has a naive implementation, called with `--check-ref`.
+    fn check_ref(&self, day: Day, options: InputOptions) -> Outcome {
+        if self.naive_one.is_none() && self.naive_two.is_none() {
+            error::report(&Error::InvalidArguments(
+                "this day has no naive implementation, register one with `solution!(DD, naive_one = ...)`.".into(),
+            ));
+            return Outcome::Error;
+        }
+
+        let outcome_one = self.naive_one.map_or(Outcome::Success, |naive| {
+            check_ref::check(1, self.part_one, naive, &self.check_inputs(day, 1, options))
+        });
+        let outcome_two = self.naive_two.map_or(Outcome::Success, |naive| {
+            check_ref::check(2, self.part_two, naive, &self.check_inputs(day, 2, options))
+        });
+        outcome_one.max(outcome_two)
+    }
+
+    /// Collects the example of `part` and small generated inputs.
+    /// Days without a generator are checked on prefixes of the puzzle input instead.
+    fn check_inputs(&self, day: Day, part: u8, options: InputOptions) -> Vec<CheckInput> {
+        let mut inputs = vec![];
+
+        let example = try_read_file_part("examples", day, part)
+            .map(|input| (input, format!("the example of part {part}")))
+            .or_else(|_| try_read_file("examples", day).map(|input| (input, "the example".into())));
+        if let Ok((input, description)) = example {
+            inputs.push(CheckInput {
+                description,
+                input,
+                may_be_invalid: false,
+            });
+        }
+
+        if let Some(generator) = self.generator {
+            for size in CHECK_SIZES {
+                for seed in CHECK_SEEDS {
+                    inputs.push(CheckInput {
+                        description: format!(
+                            "the input of `cargo gen {day} --size {size} --seed {seed}`"
+                        ),
+                        input: generator(&mut Rng::new(seed), size),
+                        may_be_invalid: false,
+                    });
+                }
+            }
+        } else if let Ok(input) = read_input(day, options) {
+            let lines: Vec<&str> = input.lines().collect();
+            let lengths = std::iter::successors(Some(1), |length| Some(length * 2));
+            for length in lengths.take_while(|length| *length < lines.len()) {
+                inputs.push(CheckInput {
+                    description: format!("the first {length} lines of the input"),
+                    input: lines[..length].join("\n") + "\n",
+                    may_be_invalid: true,
+                });
+            }
+        }
+
+        inputs
+    }
+}
+
+/// Parses the value after `flag`, [`None`] if the flag is missing.
+fn parse_value<T: std::str::FromStr>(args: &[String], flag: &str) -> Result<Option<T>, Error> {
+    let Some(index) = args.iter().position(|arg| arg == flag) else {
+        return Ok(None);
+    };
+
+    args.get(index + 1)
+        .and_then(|value| value.parse().ok())
+        .map(Some)
+        .ok_or_else(|| Error::InvalidArguments(format!("invalid value for `{flag}`.")))
+}
+
+fn report(result: Result<Outcome, Error>) -> Outcome {
+    match result {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            error::report(&e);
+            Outcome::Error
+        }
+    }
+}
@@ -0,0 +1,506 @@
+/// Tracing support for solution binaries, enabled with `cargo solve DD --trace [filter]`.
+/// Events are printed to stderr, filtered like `RUST_LOG` (see [`EnvFilter`]).
+/// The time spent in each span is recorded and summarized per part once the binary exits.
+///
+/// With `--trace-out <file>`, every span is also written in the Chrome Trace Event format
+/// (open it in `about:tracing` or Perfetto), with `--trace-folded <file>` as folded stacks for flamegraph tools like inferno.
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Write};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use std::{env, fs};
+
+use serde::Serialize;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::filter::{filter_fn, EnvFilter, FilterExt};
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{fmt, Layer};
+
+use crate::template::error::{self, Context as _, Error};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// The name of the span that the runner opens around each part.
+pub const PART_SPAN: &str = "part";
+
+/// The field of the part span that holds the number of the part.
+pub const PART_FIELD: &str = "part";
+
+/// The filter used by `--trace` without a value, unless `RUST_LOG` is set.
+pub const DEFAULT_FILTER: &str = "info";
+
+/// Returns the filter for `--trace` without a value.
+#[must_use]
+pub fn default_filter() -> String {
+    env::var(EnvFilter::DEFAULT_ENV)
+        .ok()
+        .filter(|filter| !filter.is_empty())
+        .unwrap_or_else(|| DEFAULT_FILTER.into())
+}
+
+/// Parses a filter directive like `info` or `day_05=debug,[parse_maps]=trace`.
+pub fn parse_filter(filter: &str) -> Result<EnvFilter, Error> {
+    EnvFilter::try_new(filter)
+        .map_err(|e| Error::InvalidArguments(format!("invalid trace filter \"{filter}\": {e}")))
+}
+
+/// The tracing flags passed to a solution binary.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TraceArgs {
+    /// Print events matching this filter, from `--trace`.
+    filter: Option<String>,
+    /// Write a Chrome trace to this file, from `--trace-out`.
+    out: Option<PathBuf>,
+    /// Write folded stacks to this file, from `--trace-folded`.
+    folded: Option<PathBuf>,
+}
+
+impl TraceArgs {
+    fn parse(args: &[String]) -> Self {
+        let value = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)?;
+            Some(
+                args.get(index + 1)
+                    .filter(|value| !value.starts_with("--"))
+                    .cloned(),
+            )
+        };
+
+        Self {
+            filter: value("--trace").map(|filter| filter.unwrap_or_else(default_filter)),
+            out: value("--trace-out").flatten().map(PathBuf::from),
+            folded: value("--trace-folded").flatten().map(PathBuf::from),
+        }
+    }
+}
+
+/// Installs a subscriber if the binary was called with `--trace <filter>`, `--trace-out <file>` or `--trace-folded <file>`.
+/// Span timings are printed (and trace files written) when the returned guard is dropped.
+#[must_use]
+pub fn init() -> Option<TraceGuard> {
+    let args: Vec<String> = env::args().collect();
+    let args = TraceArgs::parse(&args);
+
+    if args.filter.is_none() && args.out.is_none() && args.folded.is_none() {
+        return None;
+    }
+
+    START.get_or_init(Instant::now);
+    let filter = args.filter.clone().unwrap_or_else(default_filter);
+
+    // filters can't be cloned, every layer gets its own.
+    let (fmt_filter, timing_filter) = match parse_filter(&filter).and_then(|fmt_filter| {
+        parse_filter(&filter).map(|timing_filter| (fmt_filter, timing_filter))
+    }) {
+        Ok(filters) => filters,
+        Err(e) => {
+            error::report(&e);
+            return None;
+        }
+    };
+
+    // events are only printed with `--trace`, trace files alone are quiet.
+    let fmt_layer = args.filter.is_some().then(|| {
+        fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(fmt_filter)
+    });
+
+    // part spans are always recorded so that the summary can be grouped by part.
+    let is_part_span = filter_fn(|meta| meta.is_span() && meta.name() == PART_SPAN);
+
+    let timings = SpanTimings {
+        record_intervals: args.out.is_some(),
+    };
+
+    let subscriber = tracing_subscriber::registry()
+        .with(fmt_layer)
+        .with(timings.with_filter(timing_filter.or(is_part_span)));
+
+    if tracing::subscriber::set_global_default(subscriber).is_err() {
+        return None;
+    }
+
+    Some(TraceGuard {
+        out: args.out,
+        folded: args.folded,
+    })
+}
+
+/// Prints the span timings and writes trace files when dropped.
+pub struct TraceGuard {
+    out: Option<PathBuf>,
+    folded: Option<PathBuf>,
+}
+
+impl Drop for TraceGuard {
+    fn drop(&mut self) {
+        let timings = std::mem::take(&mut *lock(&TIMINGS));
+        if !timings.is_empty() {
+            eprintln!("{}", render_summary(&timings));
+        }
+
+        let files = [
+            (&self.out, chrome_trace(&lock(&INTERVALS))),
+            (&self.folded, folded_stacks(&timings)),
+        ];
+
+        for (path, content) in files {
+            let Some(path) = path else {
+                continue;
+            };
+
+            match fs::write(path, content)
+                .with_context(|| format!("failed to write trace to \"{}\"", path.display()))
+            {
+                Ok(()) => eprintln!("Wrote trace to \"{}\".", path.display()),
+                Err(e) => error::report(&e),
+            }
+        }
+    }
+}
+
+/// Aggregated timings of all spans that share a path, e.g. `part 1 > parse_maps`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct Timing {
+    calls: u64,
+    busy: Duration,
+}
+
+/// A single stretch of time in which a span was entered, for the Chrome trace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Interval {
+    path: Vec<String>,
+    thread: u64,
+    start: Duration,
+    duration: Duration,
+}
+
+static TIMINGS: Mutex<BTreeMap<Vec<String>, Timing>> = Mutex::new(BTreeMap::new());
+static INTERVALS: Mutex<Vec<Interval>> = Mutex::new(Vec::new());
+
+/// The time all intervals are relative to.
+static START: OnceLock<Instant> = OnceLock::new();
+
+fn lock<T>(mutex: &'static Mutex<T>) -> std::sync::MutexGuard<'static, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
+
+thread_local! {
+    /// A small, stable id of the current thread for the Chrome trace.
+    static THREAD_ID: Cell<u64> = const { Cell::new(0) };
+}
+
+fn thread_id() -> u64 {
+    THREAD_ID.with(|id| {
+        if id.get() == 0 {
+            id.set(NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed));
+        }
+        id.get()
+    })
+}
+
+/// Per-span state, kept in the span's extensions while it is open.
+struct SpanState {
+    path: Vec<String>,
+    busy: Duration,
+    entered_at: Option<Instant>,
+}
+
+/// A layer that measures the time spent inside each span (excluding time where the span was not entered).
+struct SpanTimings {
+    /// Keep every enter/exit interval in addition to the aggregated timings.
+    record_intervals: bool,
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanTimings {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+
+        let mut path = span
+            .parent()
+            .and_then(|parent| {
+                parent
+                    .extensions()
+                    .get::<SpanState>()
+                    .map(|state| state.path.clone())
+            })
+            .unwrap_or_default();
+
+        let mut label = attrs.metadata().name().to_string();
+        if label == PART_SPAN {
+            let mut visitor = PartVisitor(None);
+            attrs.record(&mut visitor);
+            if let Some(part) = visitor.0 {
+                label = format!("{label} {part}");
+            }
+        }
+        path.push(label);
+
+        span.extensions_mut().insert(SpanState {
+            path,
+            busy: Duration::ZERO,
+            entered_at: None,
+        });
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        with_state(id, &ctx, |state| state.entered_at = Some(Instant::now()));
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        with_state(id, &ctx, |state| {
+            let Some(entered_at) = state.entered_at.take() else {
+                return;
+            };
+
+            let duration = entered_at.elapsed();
+            state.busy += duration;
+
+            if self.record_intervals {
+                let start = *START.get_or_init(Instant::now);
+                lock(&INTERVALS).push(Interval {
+                    path: state.path.clone(),
+                    thread: thread_id(),
+                    start: entered_at.saturating_duration_since(start),
+                    duration,
+                });
+            }
+        });
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+
+        let extensions = span.extensions();
+        let Some(state) = extensions.get::<SpanState>() else {
+            return;
+        };
+
+        let mut timings = lock(&TIMINGS);
+        let timing = timings.entry(state.path.clone()).or_default();
+        timing.calls += 1;
+        timing.busy += state.busy;
+    }
+}
+
+fn with_state<S: Subscriber + for<'a> LookupSpan<'a>>(
+    id: &Id,
+    ctx: &Context<'_, S>,
+    func: impl FnOnce(&mut SpanState),
+) {
+    if let Some(span) = ctx.span(id) {
+        if let Some(state) = span.extensions_mut().get_mut::<SpanState>() {
+            func(state);
+        }
+    }
+}
+
+/// Reads the `part` field of a part span.
+struct PartVisitor(Option<u64>);
+
+impl Visit for PartVisitor {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        if field.name() == PART_FIELD {
+            self.0 = Some(value);
+        }
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record_u64(field, value.unsigned_abs());
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn Debug) {}
+}
+
+/// Renders the timings as an indented tree, with the share of the enclosing part for every span.
+fn render_summary(timings: &BTreeMap<Vec<String>, Timing>) -> String {
+    let mut lines = vec![format!("{ANSI_BOLD}Span timings:{ANSI_RESET}")];
+
+    for (path, timing) in timings {
+        let root = timings.get(&path[..1]).map(|root| root.busy);
+        let share = match root {
+            Some(root) if path.len() > 1 && !root.is_zero() => {
+                format!(
+                    " {:>5.1}%",
+                    timing.busy.as_secs_f64() / root.as_secs_f64() * 100.0
+                )
+            }
+            _ => String::new(),
+        };
+
+        lines.push(format!(
+            "{:indent$}{} ({:.1?}, {} {}){share}",
+            "",
+            path[path.len() - 1],
+            timing.busy,
+            timing.calls,
+            if timing.calls == 1 { "call" } else { "calls" },
+            indent = (path.len() - 1) * 2,
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/// A complete ("X") event of the Chrome Trace Event format, with timestamps in microseconds.
+#[derive(Debug, Serialize)]
+struct ChromeEvent<'a> {
+    name: &'a str,
+    cat: &'a str,
+    ph: &'static str,
+    ts: f64,
+    dur: f64,
+    pid: u32,
+    tid: u64,
+}
+
+/// Renders intervals as Chrome trace events, one per line.
+fn chrome_trace(intervals: &[Interval]) -> String {
+    let micros = |duration: Duration| duration.as_nanos() as f64 / 1000.0;
+    let mut json = b"[".to_vec();
+
+    for (i, interval) in intervals.iter().enumerate() {
+        if i > 0 {
+            json.push(b',');
+        }
+
+        let event = ChromeEvent {
+            name: interval.path.last().map_or("", String::as_str),
+            cat: &interval.path[0],
+            ph: "X",
+            ts: micros(interval.start),
+            dur: micros(interval.duration),
+            pid: 1,
+            tid: interval.thread,
+        };
+        json.push(b'\n');
+        serde_json::to_writer(&mut json, &event).expect("a trace event should serialize");
+    }
+
+    json.extend_from_slice(b"\n]\n");
+    String::from_utf8(json).expect("JSON should be UTF-8")
+}
+
+/// Renders the timings as folded stacks (`part 1;part_one;parse_maps 62`), weighted by self time in microseconds.
+fn folded_stacks(timings: &BTreeMap<Vec<String>, Timing>) -> String {
+    let mut folded = String::new();
+
+    for (path, timing) in timings {
+        let children: Duration = timings
+            .iter()
+            .filter(|(child, _)| child.len() == path.len() + 1 && child.starts_with(path))
+            .map(|(_, child)| child.busy)
+            .sum();
+
+        let self_time = timing.busy.saturating_sub(children).as_micros();
+        if self_time > 0 {
+            let _ = writeln!(folded, "{} {self_time}", path.join(";"));
+        }
+    }
+
+    folded
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{chrome_trace, folded_stacks, render_summary, Interval, Timing, TraceArgs};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn path(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    fn timings() -> BTreeMap<Vec<String>, Timing> {
+        BTreeMap::from([
+            (
+                path(&["part 1"]),
+                Timing {
+                    calls: 1,
+                    busy: Duration::from_millis(10),
+                },
+            ),
+            (
+                path(&["part 1", "parse_maps"]),
+                Timing {
+                    calls: 2,
+                    busy: Duration::from_millis(4),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn parses_trace_args() {
+        let args =
+            |args: &str| TraceArgs::parse(&args.split(' ').map(String::from).collect::<Vec<_>>());
+
+        assert_eq!(args("05 --time"), TraceArgs::default());
+        assert_eq!(
+            args("05 --trace debug --trace-out trace.json")
+                .filter
+                .as_deref(),
+            Some("debug")
+        );
+        assert_eq!(
+            args("05 --trace-out trace.json --trace-folded --time"),
+            TraceArgs {
+                filter: None,
+                out: Some("trace.json".into()),
+                folded: None,
+            }
+        );
+    }
+
+    #[test]
+    fn renders_nested_spans() {
+        assert_eq!(
+            render_summary(&timings()),
+            [
+                format!("{ANSI_BOLD}Span timings:{ANSI_RESET}"),
+                "part 1 (10.0ms, 1 call)".into(),
+                "  parse_maps (4.0ms, 2 calls)  40.0%".into(),
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_folded_stacks_with_self_time() {
+        assert_eq!(
+            folded_stacks(&timings()),
+            "part 1 6000\npart 1;parse_maps 4000\n"
+        );
+    }
+
+    #[test]
+    fn renders_chrome_trace() {
+        let intervals = [Interval {
+            path: path(&["part 2", "say \"hi\""]),
+            thread: 1,
+            start: Duration::from_micros(5),
+            duration: Duration::from_nanos(1500),
+        }];
+
+        assert_eq!(
+            chrome_trace(&intervals),
+            "[\n{\"name\":\"say \\\"hi\\\"\",\"cat\":\"part 2\",\"ph\":\"X\",\"ts\":5.0,\"dur\":1.5,\"pid\":1,\"tid\":1}\n]\n"
+        );
+        assert_eq!(chrome_trace(&[]), "[\n]\n");
+    }
+}
@@ -0,0 +1,380 @@
+//! Renders grids with colored and highlighted cells, to inspect what a solution does.
+//! A [`Canvas`] is rendered as ANSI colored text for the terminal or as SVG, which also works for grids
+//! that are too large for a terminal. [`Frames`] collects canvases of the steps of an algorithm.
+//!
+//! Solutions register a visualizer with `solution!(DD, visualizer = visualize)`, which runs with
+//! `cargo solve DD --visualize <dir>`.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+use glam::IVec2;
+
+/// The width and height of a cell in SVG output, in pixels.
+const SVG_CELL: i32 = 12;
+
+/// A 24-bit color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0x1e, 0x1e, 0x2e);
+    pub const WHITE: Color = Color(0xcd, 0xd6, 0xf4);
+    pub const GRAY: Color = Color(0x6c, 0x70, 0x86);
+    pub const RED: Color = Color(0xf3, 0x8b, 0xa8);
+    pub const GREEN: Color = Color(0xa6, 0xe3, 0xa1);
+    pub const YELLOW: Color = Color(0xf9, 0xe2, 0xaf);
+    pub const BLUE: Color = Color(0x89, 0xb4, 0xfa);
+    pub const PURPLE: Color = Color(0xcb, 0xa6, 0xf7);
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// A character with an optional text color and an optional background highlight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub char: char,
+    pub color: Option<Color>,
+    pub highlight: Option<Color>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            char: ' ',
+            color: None,
+            highlight: None,
+        }
+    }
+}
+
+/// A grid of cells, indexed by `IVec2` with `x` as the column and `y` as the row.
+/// Writes to positions outside of the canvas are ignored, so visualizations don't need their own bounds checks.
+///
+/// ```
+/// # use advent_of_code::visualize::{Canvas, Color};
+/// # use glam::IVec2;
+/// let mut canvas = Canvas::from_text("..#\n.#.");
+/// canvas.highlight(IVec2::new(2, 0), Color::YELLOW);
+/// assert_eq!(canvas.to_plain(), "..#\n.#.\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Canvas {
+    /// Creates an empty canvas of spaces.
+    #[must_use]
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![Cell::default(); width * height],
+        }
+    }
+
+    /// Creates a canvas with the characters of `text`, shorter lines are padded with spaces.
+    #[must_use]
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut canvas = Self::new(width, lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            for (x, char) in line.chars().enumerate() {
+                canvas.cells[y * width + x].char = char;
+            }
+        }
+        canvas
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn index(&self, pos: IVec2) -> Option<usize> {
+        let x = usize::try_from(pos.x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(pos.y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    #[must_use]
+    pub fn get(&self, pos: IVec2) -> Option<&Cell> {
+        self.index(pos).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, pos: IVec2) -> Option<&mut Cell> {
+        self.index(pos).map(|index| &mut self.cells[index])
+    }
+
+    /// Replaces the character of a cell, keeping its colors.
+    pub fn set(&mut self, pos: IVec2, char: char) {
+        if let Some(cell) = self.get_mut(pos) {
+            cell.char = char;
+        }
+    }
+
+    /// Sets the text color of a cell.
+    pub fn color(&mut self, pos: IVec2, color: Color) {
+        if let Some(cell) = self.get_mut(pos) {
+            cell.color = Some(color);
+        }
+    }
+
+    /// Sets the background color of a cell.
+    pub fn highlight(&mut self, pos: IVec2, color: Color) {
+        if let Some(cell) = self.get_mut(pos) {
+            cell.highlight = Some(color);
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        // `max(1)` keeps `chunks` from panicking on an empty canvas.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Renders the characters without colors, one line per row.
+    #[must_use]
+    pub fn to_plain(&self) -> String {
+        self.rows()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.char)
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    /// Renders the canvas with ANSI escape codes for 24-bit colors, one line per row.
+    #[must_use]
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+
+        for row in self.rows() {
+            for cell in row {
+                if let Some(Color(r, g, b)) = cell.color {
+                    let _ = write!(ansi, "\x1b[38;2;{r};{g};{b}m");
+                }
+                if let Some(Color(r, g, b)) = cell.highlight {
+                    let _ = write!(ansi, "\x1b[48;2;{r};{g};{b}m");
+                }
+                ansi.push(cell.char);
+                if cell.color.is_some() || cell.highlight.is_some() {
+                    ansi.push_str("\x1b[0m");
+                }
+            }
+            ansi.push('\n');
+        }
+
+        ansi
+    }
+
+    /// Renders the canvas as an SVG image with a monospace character per cell.
+    #[must_use]
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (self.width as i32 * SVG_CELL, self.height as i32 * SVG_CELL);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n\
+             <g font-family=\"monospace\" font-size=\"{SVG_CELL}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"{}\">\n",
+            Color::BLACK.hex(),
+            Color::WHITE.hex(),
+        );
+
+        for (y, row) in self.rows().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let (left, top) = (x as i32 * SVG_CELL, y as i32 * SVG_CELL);
+
+                if let Some(highlight) = cell.highlight {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{left}\" y=\"{top}\" width=\"{SVG_CELL}\" height=\"{SVG_CELL}\" fill=\"{}\"/>",
+                        highlight.hex()
+                    );
+                }
+
+                if !cell.char.is_whitespace() {
+                    let fill = cell
+                        .color
+                        .map(|color| format!(" fill=\"{}\"", color.hex()))
+                        .unwrap_or_default();
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{}\" y=\"{}\"{fill}>{}</text>",
+                        left + SVG_CELL / 2,
+                        top + SVG_CELL / 2,
+                        escape_xml(cell.char)
+                    );
+                }
+            }
+        }
+
+        svg.push_str("</g>\n</svg>\n");
+        svg
+    }
+
+    /// Writes the SVG rendering to `path`.
+    pub fn write_svg(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_svg())
+    }
+}
+
+fn escape_xml(char: char) -> String {
+    match char {
+        '&' => "&amp;".into(),
+        '<' => "&lt;".into(),
+        '>' => "&gt;".into(),
+        '"' => "&quot;".into(),
+        char => char.to_string(),
+    }
+}
+
+/// The canvases of the steps of an algorithm.
+#[derive(Debug, Clone, Default)]
+pub struct Frames {
+    frames: Vec<Canvas>,
+}
+
+impl Frames {
+    pub fn push(&mut self, canvas: Canvas) {
+        self.frames.push(canvas);
+    }
+
+    #[must_use]
+    pub fn frames(&self) -> &[Canvas] {
+        &self.frames
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Writes every frame to `dir` as `{name}-001.svg`, `{name}-002.svg`, ... and returns the paths.
+    /// The directory is created if needed.
+    pub fn write_svgs(&self, dir: impl AsRef<Path>, name: &str) -> io::Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let digits = self.frames.len().to_string().len().max(3);
+        self.frames
+            .iter()
+            .enumerate()
+            .map(|(index, frame)| {
+                let path = dir.join(format!("{name}-{:0digits$}.svg", index + 1));
+                frame.write_svg(&path)?;
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Plays the frames in the terminal, redrawing the screen after each `delay`.
+    pub fn play(&self, delay: Duration) {
+        for frame in &self.frames {
+            print!("\x1b[H\x1b[2J{}", frame.to_ansi());
+            thread::sleep(delay);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Canvas, Color, Frames};
+    use glam::IVec2;
+    use std::fs;
+
+    #[test]
+    fn reads_text() {
+        let canvas = Canvas::from_text("ab\nc\n");
+
+        assert_eq!((canvas.width(), canvas.height()), (2, 2));
+        assert_eq!(canvas.get(IVec2::new(1, 0)).unwrap().char, 'b');
+        assert_eq!(canvas.get(IVec2::new(1, 1)).unwrap().char, ' ');
+        assert_eq!(canvas.get(IVec2::new(2, 0)), None);
+        assert_eq!(canvas.get(IVec2::new(0, -1)), None);
+        assert_eq!(canvas.to_plain(), "ab\nc \n");
+    }
+
+    #[test]
+    fn ignores_writes_outside() {
+        let mut canvas = Canvas::new(2, 1);
+        canvas.set(IVec2::new(5, 0), 'x');
+        canvas.color(IVec2::new(-1, 0), Color::RED);
+        assert_eq!(canvas, Canvas::new(2, 1));
+    }
+
+    #[test]
+    fn renders_ansi() {
+        let mut canvas = Canvas::from_text("ab");
+        canvas.color(IVec2::new(0, 0), Color(1, 2, 3));
+        canvas.highlight(IVec2::new(0, 0), Color(4, 5, 6));
+
+        assert_eq!(
+            canvas.to_ansi(),
+            "\x1b[38;2;1;2;3m\x1b[48;2;4;5;6ma\x1b[0mb\n"
+        );
+    }
+
+    #[test]
+    fn renders_svg() {
+        let mut canvas = Canvas::from_text("<. \n&#");
+        canvas.highlight(IVec2::new(1, 1), Color(0xff, 0, 0x10));
+        canvas.color(IVec2::new(1, 0), Color::GREEN);
+        let svg = canvas.to_svg();
+
+        assert!(
+            svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"36\" height=\"24\"")
+        );
+        assert!(svg.contains("<text x=\"6\" y=\"6\">&lt;</text>"));
+        assert!(svg.contains("<text x=\"18\" y=\"6\" fill=\"#a6e3a1\">.</text>"));
+        assert!(svg.contains("<text x=\"6\" y=\"18\">&amp;</text>"));
+        assert!(
+            svg.contains("<rect x=\"12\" y=\"12\" width=\"12\" height=\"12\" fill=\"#ff0010\"/>")
+        );
+        // spaces are not drawn.
+        assert_eq!(svg.matches("<text").count(), 4);
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn writes_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut frames = Frames::default();
+        frames.push(Canvas::from_text("a"));
+        frames.push(Canvas::from_text("b"));
+
+        let paths = frames.write_svgs(&dir, "step").unwrap();
+
+        assert_eq!(
+            paths,
+            vec![dir.join("step-001.svg"), dir.join("step-002.svg")]
+        );
+        assert!(fs::read_to_string(&paths[1]).unwrap().contains(">b</text>"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}